@@ -0,0 +1,476 @@
+//! A minimal GSI server for synchronous applications.
+//!
+//! Unlike the `server` feature, this doesn't require an async runtime. Events are received on
+//! background threads using [`std::net`] and passed along through a [`mpsc::Receiver`].
+//!
+//! ```no_run
+//! use schnose_gsi::blocking::{Config, Server};
+//!
+//! let config = Config::builder().build("my-tool", 8080_u16);
+//! let (events, kill_signal) = Server::new(config).start().unwrap();
+//!
+//! for event in events.iter().take(100) {
+//!     println!("{:?}", event.map);
+//! }
+//!
+//! kill_signal.kill();
+//! ```
+
+use {
+	crate::{csgo::Delivery, Error, Event, Result},
+	std::{
+		fmt,
+		io::{self, BufRead, BufReader, Write},
+		net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+		result::Result as StdResult,
+		sync::{
+			atomic::{AtomicBool, Ordering},
+			mpsc, Arc,
+		},
+		thread,
+		time::Duration,
+	},
+	tracing::debug,
+};
+
+pub use crate::config::{Config, ConfigBuilder, ConfigFile, Subscription};
+
+/// Payloads larger than this are rejected with `413 Payload Too Large`.
+const MAX_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+
+/// Request lines and headers larger than this in total are rejected with
+/// `431 Request Header Fields Too Large`.
+const MAX_HEADER_SIZE: u64 = 8 * 1024;
+
+/// Requests with more headers than this are rejected with `431 Request Header Fields Too Large`.
+const MAX_HEADERS: usize = 64;
+
+/// Connections that don't send anything for this long are closed.
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long [`KillSignal::kill`] tries to wake up the listener.
+const WAKE_UP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Blocking HTTP listener for GSI events.
+///
+/// Every connection is handled on its own thread. Connections are closed once the
+/// [`mpsc::Receiver`] returned by [`Server::start`] has been dropped, or after not sending anything
+/// for a minute. The listener keeps running until it is stopped with the returned [`KillSignal`].
+#[derive(Debug, Clone)]
+pub struct Server {
+	/// The config for this server.
+	config: Config,
+
+	/// The address of the interface to listen on.
+	ip: IpAddr,
+}
+
+impl Server {
+	/// Constructs a new [`Server`] with the given config.
+	pub fn new(config: Config) -> Self {
+		Self { config, ip: IpAddr::V4(Ipv4Addr::LOCALHOST) }
+	}
+
+	/// Sets the address of the interface to listen on. Defaults to `127.0.0.1`.
+	pub fn listen_on(mut self, ip: impl Into<IpAddr>) -> Self {
+		self.ip = ip.into();
+		self
+	}
+
+	/// Binds to [`Config::service_port`] and starts a background thread accepting connections.
+	///
+	/// The returned [`mpsc::Receiver`] can be used to receive events, either one by one or by
+	/// iterating over it. You can use the returned [`KillSignal`] to stop the server later.
+	pub fn start(self) -> Result<(mpsc::Receiver<Event>, KillSignal)> {
+		let addr = SocketAddr::new(self.ip, self.config.service_port);
+		let listener = TcpListener::bind(addr).map_err(|error| Error::Bind(error.to_string()))?;
+		let addr = listener
+			.local_addr()
+			.map_err(|error| Error::Bind(error.to_string()))?;
+
+		let (event_emitter, event_listener) = mpsc::channel();
+		let stopped = Arc::new(AtomicBool::new(false));
+		let kill_signal = KillSignal { stopped: Arc::clone(&stopped), addr };
+
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				if stopped.load(Ordering::Relaxed) {
+					break;
+				}
+
+				let stream = match stream {
+					Ok(stream) => stream,
+					Err(error) => {
+						debug!(%error, "failed to accept connection");
+						continue;
+					}
+				};
+
+				let event_emitter = event_emitter.clone();
+				let stopped = Arc::clone(&stopped);
+
+				thread::spawn(move || {
+					if let Err(error) = handle_connection(stream, &event_emitter, &stopped) {
+						debug!(%error, "connection failed");
+					}
+				});
+			}
+		});
+
+		Ok((event_listener, kill_signal))
+	}
+}
+
+/// Signal for shutting down the [`Server`] you got this from.
+///
+/// Dropping it without calling [`KillSignal::kill`] keeps the server running.
+#[derive(Debug)]
+pub struct KillSignal {
+	stopped: Arc<AtomicBool>,
+
+	/// The address the server is bound to.
+	addr: SocketAddr,
+}
+
+impl KillSignal {
+	/// Shuts down the [`Server`] you got this signal from.
+	///
+	/// The listener stops accepting connections and open connections are closed after their
+	/// current request.
+	pub fn kill(self) {
+		self.stopped.store(true, Ordering::Relaxed);
+
+		// Wake up the listener, which is blocked waiting for the next connection.
+		let ip = match self.addr.ip() {
+			IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+			IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+			ip => ip,
+		};
+
+		if let Err(error) =
+			TcpStream::connect_timeout(&SocketAddr::new(ip, self.addr.port()), WAKE_UP_TIMEOUT)
+		{
+			debug!(%error, "failed to wake up listener");
+		}
+	}
+}
+
+/// A parsed HTTP request.
+struct Request {
+	method: String,
+	body: Vec<u8>,
+
+	/// Whether the client asked to close the connection after this request.
+	close: bool,
+}
+
+/// Why a request couldn't be read.
+#[derive(Debug)]
+enum ReadError {
+	/// The connection failed or timed out.
+	Io(io::Error),
+
+	/// The request is malformed. Rejected with `400 Bad Request`.
+	Malformed(&'static str),
+
+	/// The request line and headers are larger than [`MAX_HEADER_SIZE`] or there are more than
+	/// [`MAX_HEADERS`] headers. Rejected with `431 Request Header Fields Too Large`.
+	HeadTooLarge,
+
+	/// The body is larger than [`MAX_PAYLOAD_SIZE`]. Rejected with `413 Payload Too Large`.
+	TooLarge,
+
+	/// The body uses a `Transfer-Encoding` (e.g. `chunked`), which isn't supported. Rejected with
+	/// `501 Not Implemented`.
+	TransferEncoding,
+}
+
+impl ReadError {
+	/// The status the request is rejected with.
+	fn status(&self) -> &'static str {
+		match self {
+			Self::Io(_) | Self::Malformed(_) => "400 Bad Request",
+			Self::HeadTooLarge => "431 Request Header Fields Too Large",
+			Self::TooLarge => "413 Payload Too Large",
+			Self::TransferEncoding => "501 Not Implemented",
+		}
+	}
+}
+
+impl fmt::Display for ReadError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Io(error) => write!(f, "{error}"),
+			Self::Malformed(reason) => f.write_str(reason),
+			Self::HeadTooLarge => f.write_str("request line and headers too large"),
+			Self::TooLarge => f.write_str("payload too large"),
+			Self::TransferEncoding => f.write_str("unsupported `Transfer-Encoding`"),
+		}
+	}
+}
+
+impl From<io::Error> for ReadError {
+	fn from(error: io::Error) -> Self {
+		Self::Io(error)
+	}
+}
+
+/// Handles requests on a single connection until it's closed.
+fn handle_connection(
+	stream: TcpStream,
+	event_emitter: &mpsc::Sender<Event>,
+	stopped: &AtomicBool,
+) -> io::Result<()> {
+	stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+	let remote_addr = stream.peer_addr().ok();
+	let mut reader = BufReader::new(stream.try_clone()?);
+	let mut writer = stream;
+
+	while !stopped.load(Ordering::Relaxed) {
+		let request = match read_request(&mut reader) {
+			Ok(Some(request)) => request,
+			Ok(None) => return Ok(()),
+			Err(ReadError::Io(error))
+				if matches!(error.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) =>
+			{
+				debug!("closing idle connection");
+				return Ok(());
+			}
+			Err(ReadError::Io(error)) => return Err(error),
+			Err(error) => {
+				debug!(%error, "rejecting request");
+				return write_response(&mut writer, error.status());
+			}
+		};
+
+		let status = if request.method != "POST" {
+			"405 Method Not Allowed"
+		} else {
+			match serde_json::from_slice::<Event>(&request.body) {
+				Ok(mut event) => {
+					event.delivery = Some(Delivery { remote_addr, ..Delivery::now(&request.body) });
+
+					if event_emitter.send(event).is_err() {
+						// Nobody is listening anymore.
+						return Ok(());
+					}
+
+					"200 OK"
+				}
+				Err(error) => {
+					debug!(%error, "failed to parse event");
+					"400 Bad Request"
+				}
+			}
+		};
+
+		write_response(&mut writer, status)?;
+
+		if request.close {
+			return Ok(());
+		}
+	}
+
+	Ok(())
+}
+
+/// Reads a single request from `reader`.
+///
+/// Returns [`None`] if the connection was closed before a request was sent.
+fn read_request<R: BufRead>(reader: &mut R) -> StdResult<Option<Request>, ReadError> {
+	let mut head = io::Read::take(&mut *reader, MAX_HEADER_SIZE);
+
+	let Some(request_line) = read_line(&mut head)? else {
+		return Ok(None);
+	};
+
+	let method = request_line
+		.split_whitespace()
+		.next()
+		.unwrap_or_default()
+		.to_owned();
+
+	let mut content_length = 0;
+	let mut close = false;
+	let mut headers = 0;
+
+	loop {
+		let Some(line) = read_line(&mut head)? else {
+			return Ok(None);
+		};
+
+		if line.is_empty() {
+			break;
+		}
+
+		headers += 1;
+
+		if headers > MAX_HEADERS {
+			return Err(ReadError::HeadTooLarge);
+		}
+
+		let Some((name, value)) = line.split_once(':') else {
+			return Err(ReadError::Malformed("header without `:`"));
+		};
+
+		let value = value.trim();
+
+		if name.eq_ignore_ascii_case("content-length") {
+			content_length = value
+				.parse()
+				.map_err(|_| ReadError::Malformed("invalid `Content-Length`"))?;
+		} else if name.eq_ignore_ascii_case("transfer-encoding") {
+			return Err(ReadError::TransferEncoding);
+		} else if name.eq_ignore_ascii_case("connection") {
+			close = value.eq_ignore_ascii_case("close");
+		}
+	}
+
+	if content_length > MAX_PAYLOAD_SIZE {
+		return Err(ReadError::TooLarge);
+	}
+
+	let mut body = vec![0; content_length];
+	reader.read_exact(&mut body)?;
+
+	Ok(Some(Request { method, body, close }))
+}
+
+/// Reads a single line of a request's head, without the line ending.
+///
+/// Returns [`None`] if the connection was closed before the line was complete.
+fn read_line(head: &mut io::Take<impl BufRead>) -> StdResult<Option<String>, ReadError> {
+	let mut line = String::new();
+	head.read_line(&mut line)?;
+
+	if let Some(line) = line.strip_suffix('\n') {
+		return Ok(Some(line.trim_end_matches('\r').to_owned()));
+	}
+
+	if head.limit() == 0 {
+		return Err(ReadError::HeadTooLarge);
+	}
+
+	Ok(None)
+}
+
+fn write_response(writer: &mut impl Write, status: &str) -> io::Result<()> {
+	write!(writer, "HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n")?;
+	writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, std::io::Cursor};
+
+	fn read(request: &str) -> StdResult<Option<Request>, ReadError> {
+		read_request(&mut Cursor::new(request))
+	}
+
+	#[test]
+	fn keep_alive() {
+		let mut reader = Cursor::new(
+			"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}\
+			 POST / HTTP/1.1\r\nContent-Length: 3\r\n\r\n{ }",
+		);
+
+		let first = read_request(&mut reader)
+			.expect("valid request")
+			.expect("request was sent");
+
+		assert_eq!(first.method, "POST");
+		assert_eq!(first.body, b"{}");
+		assert!(!first.close);
+
+		let second = read_request(&mut reader)
+			.expect("valid request")
+			.expect("request was sent");
+
+		assert_eq!(second.body, b"{ }");
+		assert!(read_request(&mut reader)
+			.expect("closed")
+			.is_none());
+	}
+
+	#[test]
+	fn connection_close() {
+		let request = read("POST / HTTP/1.1\r\nconnection: Close\r\ncontent-length: 0\r\n\r\n")
+			.expect("valid request")
+			.expect("request was sent");
+
+		assert!(request.close);
+		assert!(request.body.is_empty());
+	}
+
+	#[test]
+	fn oversized_body() {
+		let request =
+			format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_PAYLOAD_SIZE + 1);
+
+		assert!(matches!(read(&request), Err(ReadError::TooLarge)));
+	}
+
+	#[test]
+	fn bad_headers() {
+		assert!(matches!(
+			read("POST / HTTP/1.1\r\nContent-Length: two\r\n\r\n{}"),
+			Err(ReadError::Malformed(_))
+		));
+
+		assert!(matches!(
+			read("POST / HTTP/1.1\r\nContent-Length: -1\r\n\r\n"),
+			Err(ReadError::Malformed(_))
+		));
+
+		assert!(matches!(
+			read("POST / HTTP/1.1\r\nnot a header\r\n\r\n"),
+			Err(ReadError::Malformed(_))
+		));
+	}
+
+	#[test]
+	fn oversized_head() {
+		let request_line = format!("POST /{} HTTP/1.1", "a".repeat(MAX_HEADER_SIZE as usize));
+		assert!(matches!(read(&request_line), Err(ReadError::HeadTooLarge)));
+
+		let headers = "X-Header: value\r\n".repeat(MAX_HEADERS + 1);
+		let request = format!("POST / HTTP/1.1\r\n{headers}\r\n");
+		assert!(matches!(read(&request), Err(ReadError::HeadTooLarge)));
+
+		let headers = "X-Header: value\r\n".repeat(MAX_HEADERS);
+		let request = format!("POST / HTTP/1.1\r\n{headers}\r\n");
+		assert!(matches!(read(&request), Ok(Some(_))));
+	}
+
+	#[test]
+	fn chunked_body() {
+		let request = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n2\r\n{}\r\n0\r\n\r\n";
+
+		assert!(matches!(read(request), Err(ReadError::TransferEncoding)));
+	}
+
+	#[test]
+	fn truncated_request() {
+		assert!(matches!(read(""), Ok(None)));
+		assert!(matches!(read("POST / HTTP/1.1\r\nContent-Length: 2\r\n"), Ok(None)));
+		assert!(matches!(
+			read("POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\n{}"),
+			Err(ReadError::Io(_))
+		));
+	}
+
+	#[test]
+	fn kill_signal_stops_listener() {
+		let config = Config::builder().build("test", 0_u16);
+		let (events, kill_signal) = Server::new(config).start().expect("bind");
+
+		kill_signal.kill();
+
+		// The listener thread owns the last sender, so the channel closes once it stops.
+		assert_eq!(
+			events.recv_timeout(Duration::from_secs(5)),
+			Err(mpsc::RecvTimeoutError::Disconnected)
+		);
+	}
+}
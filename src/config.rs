@@ -76,13 +76,24 @@ impl Config {
 	/// SteamLibrary/steamapps/common/Counter-Strike Global Offensive/csgo/cfg
 	/// ```
 	pub fn install(&self, path: impl Into<PathBuf>) -> Result<ConfigFile> {
+		self.install_with_uri(path, format!("http://127.0.0.1:{}", self.service_port))
+	}
+
+	/// Install the config as a file into the given `path`, telling CS:GO to send events to `uri`.
+	///
+	/// See [`Config::install`].
+	pub fn install_with_uri(
+		&self,
+		path: impl Into<PathBuf>,
+		uri: impl Into<String>,
+	) -> Result<ConfigFile> {
 		let mut path = path.into();
 		let filename = format!("gamestate_integration_{}.cfg", self.service_name);
 
 		debug!(path = %path.display(), %filename, "Installing config...");
 		path.push(filename);
 
-		let uri = format!("http://127.0.0.1:{}", self.service_port);
+		let uri = uri.into();
 
 		let output = Precision {
 			precision_time: self.precision_time,
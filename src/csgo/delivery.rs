@@ -0,0 +1,189 @@
+//! Timestamps and delivery metadata of events.
+
+use {
+	super::{Event, GameInfo},
+	std::{
+		hash::{DefaultHasher, Hash, Hasher},
+		net::SocketAddr,
+		time::{Duration, SystemTime, UNIX_EPOCH},
+	},
+};
+
+/// Metadata attached to an [`Event`] by whoever received it (e.g. the `Server`).
+///
+/// This is not part of the payload CS:GO sends and is therefore never (de)serialized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Delivery {
+	/// When the event was received.
+	pub received_at: SystemTime,
+
+	/// A hash of the raw payload.
+	///
+	/// CS:GO resends the exact same payload if it didn't get a response in time, so two
+	/// deliveries with the same digest and provider timestamp are duplicates.
+	pub digest: u64,
+
+	/// The address of the game client that sent the event, if known.
+	pub remote_addr: Option<SocketAddr>,
+}
+
+impl Delivery {
+	/// Constructs the metadata for a `payload` that was received just now.
+	pub fn now(payload: &[u8]) -> Self {
+		let mut hasher = DefaultHasher::new();
+		payload.hash(&mut hasher);
+
+		Self { received_at: SystemTime::now(), digest: hasher.finish(), remote_addr: None }
+	}
+}
+
+/// How an [`Event`] relates to the event that was received before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeliveryOrder {
+	/// The event was generated after the previous one.
+	InOrder,
+
+	/// The event is a resent copy of the previous one.
+	Duplicate,
+
+	/// The event was generated before the previous one.
+	OutOfOrder,
+}
+
+impl GameInfo {
+	/// The time at which this update was generated.
+	///
+	/// CS:GO only reports full seconds.
+	pub fn time(&self) -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(self.timestamp)
+	}
+
+	/// The time at which this update was generated.
+	///
+	/// CS:GO only reports full seconds.
+	#[cfg(feature = "chrono")]
+	pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+		chrono::DateTime::<chrono::Utc>::from(self.time())
+	}
+}
+
+/// Compares everything CS:GO sent, but not the [`Delivery`] metadata.
+impl PartialEq for Event {
+	fn eq(&self, other: &Self) -> bool {
+		let Self { provider, auth, player, map, round, all_players, delivery: _ } = self;
+
+		*provider == other.provider
+			&& *auth == other.auth
+			&& *player == other.player
+			&& *map == other.map
+			&& *round == other.round
+			&& *all_players == other.all_players
+	}
+}
+
+impl Event {
+	/// When this event was received, if the receiver recorded it.
+	pub fn received_at(&self) -> Option<SystemTime> {
+		self.delivery
+			.as_ref()
+			.map(|delivery| delivery.received_at)
+	}
+
+	/// The time between CS:GO generating this event and it being received.
+	///
+	/// Since CS:GO only reports full seconds, this has a precision of one second. Is [`None`] if
+	/// the event has no provider information, no receive time, or the clocks disagree.
+	pub fn latency(&self) -> Option<Duration> {
+		let generated_at = self.provider.as_ref()?.time();
+		self.received_at()?
+			.duration_since(generated_at)
+			.ok()
+	}
+
+	/// Compares this event to the `previous` one that was received.
+	///
+	/// Events without provider information are always considered [`DeliveryOrder::InOrder`].
+	pub fn delivery_order(&self, previous: &Self) -> DeliveryOrder {
+		let (Some(provider), Some(previous_provider)) =
+			(self.provider.as_ref(), previous.provider.as_ref())
+		else {
+			return DeliveryOrder::InOrder;
+		};
+
+		if provider.timestamp < previous_provider.timestamp {
+			return DeliveryOrder::OutOfOrder;
+		}
+
+		let same_digest = matches!(
+			(&self.delivery, &previous.delivery),
+			(Some(delivery), Some(previous_delivery)) if delivery.digest == previous_delivery.digest
+		);
+
+		if provider.timestamp == previous_provider.timestamp && same_digest {
+			DeliveryOrder::Duplicate
+		} else {
+			DeliveryOrder::InOrder
+		}
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use {super::*, crate::csgo::test_util};
+
+	fn event(timestamp: u64, payload: &str) -> Event {
+		let mut event = test_util::event(timestamp);
+		event.delivery = Some(Delivery::now(payload.as_bytes()));
+		event
+	}
+
+	fn received(timestamp: u64, after: Duration) -> Event {
+		let mut event = event(timestamp, "");
+		event.delivery = event.delivery.map(|delivery| Delivery {
+			received_at: UNIX_EPOCH + Duration::from_secs(timestamp) + after,
+			..delivery
+		});
+		event
+	}
+
+	#[test]
+	fn delivery_order() {
+		let previous = event(100, "a");
+
+		assert_eq!(event(101, "b").delivery_order(&previous), DeliveryOrder::InOrder);
+		assert_eq!(event(100, "b").delivery_order(&previous), DeliveryOrder::InOrder);
+		assert_eq!(event(100, "a").delivery_order(&previous), DeliveryOrder::Duplicate);
+		assert_eq!(event(99, "a").delivery_order(&previous), DeliveryOrder::OutOfOrder);
+
+		let unknown = Event { provider: None, ..event(99, "a") };
+		assert_eq!(unknown.delivery_order(&previous), DeliveryOrder::InOrder);
+		assert_eq!(previous.delivery_order(&unknown), DeliveryOrder::InOrder);
+
+		let undelivered = Event { delivery: None, ..event(100, "a") };
+		assert_eq!(undelivered.delivery_order(&previous), DeliveryOrder::InOrder);
+	}
+
+	#[test]
+	fn latency() {
+		let event = received(100, Duration::from_millis(1500));
+		assert_eq!(event.latency(), Some(Duration::from_millis(1500)));
+
+		let early = Event {
+			delivery: Some(Delivery { received_at: UNIX_EPOCH, ..Delivery::now(b"") }),
+			..event.clone()
+		};
+		assert_eq!(early.latency(), None);
+
+		assert_eq!(Event { delivery: None, ..event.clone() }.latency(), None);
+		assert_eq!(Event { provider: None, ..event }.latency(), None);
+	}
+
+	#[test]
+	fn equality_ignores_delivery() {
+		let event = received(100, Duration::ZERO);
+
+		assert_eq!(event, received(100, Duration::from_secs(3)));
+		assert_eq!(event, Event { delivery: None, ..event.clone() });
+		assert_ne!(event, received(101, Duration::ZERO));
+	}
+}
@@ -0,0 +1,258 @@
+//! Effects applied to a player (flashes, smokes, fire).
+
+use {
+	super::{Event, PlayerState},
+	std::time::{Duration, Instant},
+};
+
+/// The intensity of an effect applied to a player, ranging from `0` (none) to `255` (full).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Intensity(pub u8);
+
+impl Intensity {
+	/// The effect is applied at full strength.
+	pub const FULL: Self = Self(u8::MAX);
+	/// No effect at all.
+	pub const NONE: Self = Self(0);
+
+	/// The intensity as a fraction between `0.0` and `1.0`.
+	pub fn fraction(self) -> f64 {
+		f64::from(self.0) / f64::from(u8::MAX)
+	}
+
+	/// Whether the effect is applied at all.
+	pub const fn is_active(self) -> bool {
+		self.0 > 0
+	}
+
+	/// Whether the effect is applied at full strength.
+	pub const fn is_full(self) -> bool {
+		self.0 == u8::MAX
+	}
+}
+
+impl PlayerState {
+	/// Whether the player is flashed at all.
+	pub const fn is_flashed(&self) -> bool {
+		self.flashed.is_active()
+	}
+
+	/// Whether the player is completely blinded by a flash.
+	pub const fn is_fully_blinded(&self) -> bool {
+		self.flashed.is_full()
+	}
+
+	/// Whether the player is standing in a smoke.
+	pub const fn is_smoked(&self) -> bool {
+		self.smoked.is_active()
+	}
+
+	/// Whether the player is standing in fire.
+	pub const fn is_burning(&self) -> bool {
+		self.burning.is_active()
+	}
+}
+
+/// A single flash a player got hit by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flash {
+	/// When the flash was first observed.
+	pub started_at: Instant,
+
+	/// The highest intensity observed during the flash.
+	pub peak: Intensity,
+
+	/// How long the player was flashed. Is [`None`] while the flash is still active.
+	pub duration: Option<Duration>,
+}
+
+/// Tracks how flashed a player is across consecutive events.
+///
+/// CS:GO only reports the current flash [`Intensity`], so the remaining blind duration is
+/// estimated from how fast the intensity went down between updates. Until the intensity starts
+/// fading, [`FlashTracker::DEFAULT_FADE_DURATION`] is assumed.
+///
+/// Completed flashes are kept until [`FlashTracker::clear`] is called, e.g. when a new round or
+/// map starts.
+#[derive(Debug, Default, Clone)]
+pub struct FlashTracker {
+	/// The last observed intensity and when it was observed.
+	last: Option<(Instant, Intensity)>,
+
+	/// How much intensity is lost per second.
+	fade_rate: Option<f64>,
+
+	/// The flash that is currently active.
+	current: Option<Flash>,
+
+	/// All completed flashes.
+	flashes: Vec<Flash>,
+}
+
+impl FlashTracker {
+	/// How long a full flash is assumed to take to fade out, if no fade has been observed yet.
+	pub const DEFAULT_FADE_DURATION: Duration = Duration::from_secs(3);
+
+	/// Constructs a new [`FlashTracker`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Updates the tracker with the flash intensity observed at `at`.
+	pub fn update(&mut self, flashed: Intensity, at: Instant) {
+		let last_intensity = self
+			.last
+			.map_or(Intensity::NONE, |(_, intensity)| intensity);
+
+		if !flashed.is_active() {
+			self.finish(at);
+		} else if self.current.is_none() || flashed > last_intensity {
+			// Getting hit by another flash while still blinded counts as a new flash.
+			self.finish(at);
+			self.current = Some(Flash { started_at: at, peak: flashed, duration: None });
+		} else if let Some((last_at, last_intensity)) = self.last {
+			let elapsed = at
+				.saturating_duration_since(last_at)
+				.as_secs_f64();
+
+			if flashed < last_intensity && elapsed > 0.0 {
+				self.fade_rate = Some(f64::from(last_intensity.0 - flashed.0) / elapsed);
+			}
+		}
+
+		self.last = Some((at, flashed));
+	}
+
+	/// Updates the tracker with the current player's state from `event`, received at `at`.
+	pub fn observe(&mut self, event: &Event, at: Instant) {
+		let flashed = event
+			.player
+			.as_ref()
+			.and_then(|player| player.state.as_ref())
+			.map_or(Intensity::NONE, |state| state.flashed);
+
+		self.update(flashed, at);
+	}
+
+	/// The estimated time until the player can see again, as of the last update.
+	///
+	/// Is [`None`] if the player is not flashed.
+	pub fn remaining(&self) -> Option<Duration> {
+		let (_, intensity) = self.last?;
+
+		if !intensity.is_active() {
+			return None;
+		}
+
+		let fade_rate = self.fade_rate.unwrap_or_else(|| {
+			f64::from(Intensity::FULL.0) / Self::DEFAULT_FADE_DURATION.as_secs_f64()
+		});
+
+		Some(Duration::from_secs_f64(f64::from(intensity.0) / fade_rate))
+	}
+
+	/// The flash that is currently active.
+	pub fn current(&self) -> Option<&Flash> {
+		self.current.as_ref()
+	}
+
+	/// All flashes that have already worn off since the tracker was created or last cleared.
+	pub fn flashes(&self) -> &[Flash] {
+		&self.flashes
+	}
+
+	/// Forgets the current flash and all completed ones.
+	pub fn clear(&mut self) {
+		*self = Self::default();
+	}
+
+	/// Finishes the current flash, if there is one.
+	fn finish(&mut self, at: Instant) {
+		if let Some(mut flash) = self.current.take() {
+			flash.duration = Some(at.saturating_duration_since(flash.started_at));
+			self.flashes.push(flash);
+		}
+
+		self.fade_rate = None;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn intensity() {
+		assert!(!Intensity::NONE.is_active());
+		assert!(Intensity(1).is_active());
+		assert!(!Intensity(254).is_full());
+		assert!(Intensity::FULL.is_full());
+		assert_eq!(Intensity::FULL.fraction(), 1.0);
+		assert_eq!(Intensity::NONE.fraction(), 0.0);
+	}
+
+	#[test]
+	fn flash_is_tracked_until_it_wears_off() {
+		let start = Instant::now();
+		let mut tracker = FlashTracker::new();
+
+		tracker.update(Intensity::NONE, start);
+		assert!(tracker.current().is_none());
+		assert_eq!(tracker.remaining(), None);
+
+		tracker.update(Intensity::FULL, start);
+		assert_eq!(tracker.current().map(|flash| flash.peak), Some(Intensity::FULL));
+		assert_eq!(tracker.remaining(), Some(FlashTracker::DEFAULT_FADE_DURATION));
+
+		tracker.update(Intensity::NONE, start + Duration::from_secs(2));
+		assert!(tracker.current().is_none());
+		assert_eq!(tracker.flashes().len(), 1);
+		assert_eq!(tracker.flashes()[0].duration, Some(Duration::from_secs(2)));
+	}
+
+	#[test]
+	fn remaining_time_follows_observed_fade() {
+		let start = Instant::now();
+		let mut tracker = FlashTracker::new();
+
+		tracker.update(Intensity(200), start);
+		tracker.update(Intensity(100), start + Duration::from_secs(1));
+
+		// 100 intensity per second, 100 left.
+		assert_eq!(tracker.remaining(), Some(Duration::from_secs(1)));
+	}
+
+	#[test]
+	fn stronger_flash_starts_a_new_flash() {
+		let start = Instant::now();
+		let mut tracker = FlashTracker::new();
+
+		tracker.update(Intensity(100), start);
+		tracker.update(Intensity(50), start + Duration::from_secs(1));
+		tracker.update(Intensity::FULL, start + Duration::from_secs(2));
+
+		assert_eq!(tracker.flashes().len(), 1);
+		assert_eq!(tracker.flashes()[0].peak, Intensity(100));
+
+		let current = tracker.current().expect("flash is active");
+		assert_eq!(current.peak, Intensity::FULL);
+		assert_eq!(current.started_at, start + Duration::from_secs(2));
+	}
+
+	#[test]
+	fn clear_forgets_all_flashes() {
+		let start = Instant::now();
+		let mut tracker = FlashTracker::new();
+
+		tracker.update(Intensity::FULL, start);
+		tracker.update(Intensity::NONE, start + Duration::from_secs(1));
+		tracker.update(Intensity(100), start + Duration::from_secs(2));
+		tracker.clear();
+
+		assert!(tracker.flashes().is_empty());
+		assert!(tracker.current().is_none());
+		assert_eq!(tracker.remaining(), None);
+	}
+}
@@ -0,0 +1,617 @@
+//! Module containing all the CS:GO specific types.
+
+use std::collections::HashMap;
+
+/// Declares an enum that is (de)serialized from the plain strings CS:GO sends.
+///
+/// Every enum declared this way gets an additional `Unknown(String)` variant, so values this
+/// crate doesn't know about yet (newer game versions, community modes, etc.) still parse and
+/// preserve the raw string.
+macro_rules! string_enum {
+	(
+		$(#[$meta:meta])*
+		pub enum $name:ident {
+			$(
+				$(#[$variant_meta:meta])*
+				$variant:ident = $value:literal,
+			)*
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+		pub enum $name {
+			$(
+				$(#[$variant_meta])*
+				$variant,
+			)*
+
+			/// A value that is not known to this crate. Contains the raw string sent by CS:GO.
+			Unknown(String),
+		}
+
+		impl $name {
+			/// The string CS:GO uses to represent this value.
+			pub fn as_str(&self) -> &str {
+				match self {
+					$(Self::$variant => $value,)*
+					Self::Unknown(value) => value.as_str(),
+				}
+			}
+
+			/// Whether this is a value known to this crate.
+			pub const fn is_known(&self) -> bool {
+				!matches!(self, Self::Unknown(_))
+			}
+		}
+
+		impl From<&str> for $name {
+			fn from(value: &str) -> Self {
+				match value {
+					$($value => Self::$variant,)*
+					value => Self::Unknown(value.to_owned()),
+				}
+			}
+		}
+
+		impl From<String> for $name {
+			fn from(value: String) -> Self {
+				match Self::from(value.as_str()) {
+					Self::Unknown(_) => Self::Unknown(value),
+					known => known,
+				}
+			}
+		}
+
+		impl ::std::str::FromStr for $name {
+			type Err = ::std::convert::Infallible;
+
+			fn from_str(value: &str) -> Result<Self, Self::Err> {
+				Ok(Self::from(value))
+			}
+		}
+
+		impl ::std::fmt::Display for $name {
+			fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+				f.write_str(self.as_str())
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl serde::Serialize for $name {
+			fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+			where
+				S: serde::Serializer, {
+				serializer.serialize_str(self.as_str())
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl<'de> serde::Deserialize<'de> for $name {
+			fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+			where
+				D: serde::Deserializer<'de>, {
+				String::deserialize(deserializer).map(Self::from)
+			}
+		}
+	};
+}
+
+mod delivery;
+pub use delivery::{Delivery, DeliveryOrder};
+
+mod effect;
+pub use effect::{Flash, FlashTracker, Intensity};
+
+mod round_win;
+pub use round_win::{MatchFormat, Period, RoundWin, WinCondition};
+
+mod skin;
+pub use skin::{Finish, Skin, SkinTable};
+
+mod weapon;
+pub use weapon::{Weapon, WeaponId, WeaponState, WeaponType};
+
+#[cfg(all(test, feature = "serde"))]
+pub(crate) mod test_util;
+
+/// An event emitted by CS:GO.
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+	/// The current CS:GO instance.
+	pub provider: Option<GameInfo>,
+
+	/// Auth state.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub auth: HashMap<String, String>,
+
+	/// The current player.
+	///
+	/// If the game is spectating a player, that player will be used.
+	pub player: Option<Player>,
+
+	/// The current map.
+	///
+	/// Is [`None`] while in the main menu.
+	pub map: Option<Map>,
+
+	/// The current round of the game.
+	///
+	/// Is [`None`] while in the main menu.
+	pub round: Option<Round>,
+
+	/// Every player in the match, keyed by their `SteamID64`.
+	///
+	/// Is only sent while spectating (e.g. as GOTV or when dead on a server that allows it).
+	#[cfg_attr(feature = "serde", serde(default, rename = "allplayers"))]
+	pub all_players: HashMap<String, MatchPlayer>,
+
+	/// Information about how this event was received.
+	///
+	/// Is set by the receiver (e.g. the `Server`) and never (de)serialized. It is also ignored
+	/// when comparing events, so a resent payload is equal to the original one.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub delivery: Option<Delivery>,
+}
+
+/// Information about a CS:GO instance.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameInfo {
+	/// The name of the game.
+	pub name: String,
+
+	/// The ID of the game. `730` in the case of CS:GO.
+	#[cfg_attr(feature = "serde", serde(rename = "appid"))]
+	pub app_id: u16,
+
+	/// The version of the game.
+	pub version: u32,
+
+	/// The `SteamID` of the player playing the game.
+	#[cfg(not(feature = "gokz"))]
+	#[cfg_attr(feature = "serde", serde(rename = "steamid"))]
+	pub steam_id: String,
+
+	/// The `SteamID` of the player playing the game.
+	#[cfg(feature = "gokz")]
+	#[cfg_attr(feature = "serde", serde(rename = "steamid"))]
+	pub steam_id: gokz_rs::SteamID,
+
+	/// The timestamp of when this update was generated.
+	pub timestamp: u64,
+}
+
+/// Information about a CS:GO instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Player {
+	/// The player's name.
+	pub name: String,
+
+	/// The player's `SteamID`.
+	#[cfg(not(feature = "gokz"))]
+	#[cfg_attr(feature = "serde", serde(rename = "steamid"))]
+	pub steam_id: String,
+
+	/// The player's [`SteamID`](gokz_rs::SteamID).
+	#[cfg(feature = "gokz")]
+	#[cfg_attr(feature = "serde", serde(rename = "steamid"))]
+	pub steam_id: gokz_rs::SteamID,
+
+	/// What the player is currently doing.
+	pub activity: PlayerActivity,
+
+	/// The player's clan tag, if they have one selected.
+	pub clan: Option<String>,
+
+	/// The player's team.
+	pub team: Option<Team>,
+
+	/// The player's weapons, ordered by their slot.
+	#[cfg_attr(feature = "serde", serde(default, with = "weapon::slots"))]
+	pub weapons: Vec<Weapon>,
+
+	/// The player's current game state.
+	pub state: Option<PlayerState>,
+
+	/// The player's stats for this match.
+	pub match_stats: Option<MatchStats>,
+
+	/// The player's spectator slot.
+	pub observer_slot: Option<usize>,
+}
+
+impl Player {
+	/// The weapon the player currently has in their hands.
+	pub fn active_weapon(&self) -> Option<&Weapon> {
+		self.weapons
+			.iter()
+			.find(|weapon| weapon.weapon_state != WeaponState::Holstered)
+	}
+}
+
+/// Information about any player in the match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchPlayer {
+	/// The player's name.
+	pub name: String,
+
+	/// The player's spectator slot.
+	pub observer_slot: Option<usize>,
+
+	/// The player's team.
+	pub team: Option<Team>,
+
+	/// The player's weapons, ordered by their slot.
+	#[cfg_attr(feature = "serde", serde(default, with = "weapon::slots"))]
+	pub weapons: Vec<Weapon>,
+
+	/// The player's current game state.
+	pub state: Option<PlayerState>,
+
+	/// The player's stats for this match.
+	pub match_stats: Option<MatchStats>,
+}
+
+string_enum! {
+	/// A player's current activity.
+	pub enum PlayerActivity {
+		/// The main menu.
+		Menu = "menu",
+
+		/// In-game.
+		Playing = "playing",
+
+		/// Chat opened.
+		TextInput = "textinput",
+	}
+}
+
+string_enum! {
+	/// The two teams.
+	pub enum Team {
+		/// Terrorists.
+		T = "T",
+
+		/// Counter-Terrorists.
+		CT = "CT",
+	}
+}
+
+/// The state of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerState {
+	/// The player's health.
+	pub health: u8,
+
+	/// The player's armor.
+	pub armor: u8,
+
+	/// Whether the player is wearing a helmet.
+	pub helmet: bool,
+
+	/// How strongly the player is flashed.
+	pub flashed: Intensity,
+
+	/// How deep the player is standing in a smoke.
+	pub smoked: Intensity,
+
+	/// How strongly the player is burning.
+	pub burning: Intensity,
+
+	/// How much money the player currently has.
+	pub money: usize,
+
+	/// How many kills the player currently has.
+	///
+	/// Can be negative if the player has more deaths / team kills than actual kills.
+	#[cfg_attr(feature = "serde", serde(rename = "round_kills"))]
+	pub kills: isize,
+
+	/// How many headshots the player landed this game.
+	#[cfg_attr(feature = "serde", serde(rename = "round_killhs"))]
+	pub headshots: usize,
+
+	#[allow(missing_docs)] // No idea what this is.
+	#[cfg_attr(feature = "serde", serde(rename = "equip_value"))]
+	pub equipment_value: usize,
+
+	/// How much damage the player has dealt this round.
+	#[cfg_attr(feature = "serde", serde(rename = "round_totaldmg", alias = "round_damage"))]
+	pub round_damage: Option<usize>,
+
+	/// Whether the player is holding a defuse kit.
+	#[cfg_attr(feature = "serde", serde(rename = "defusekit"))]
+	pub defuse_kit: Option<bool>,
+}
+
+/// Stats for the current match.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchStats {
+	/// How many kills the player has made this match.
+	///
+	/// Can be negative if the player has more deaths / team kills than actual kills.
+	pub kills: isize,
+
+	/// How many kills the player has assisted with this match.
+	pub assists: usize,
+
+	/// How many times the player has died during this match.
+	pub deaths: usize,
+
+	/// How many times the player has been MVP this match.
+	pub mvps: usize,
+
+	/// How much score the player has achieved this match.
+	pub score: usize,
+}
+
+/// Information about a CS:GO instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Map {
+	/// The name of the map.
+	pub name: String,
+
+	/// The game mode being played.
+	pub mode: GameMode,
+
+	/// The current phase of the game.
+	pub phase: GamePhase,
+
+	/// The current round being played.
+	pub round: u8,
+
+	/// The amount of current spectators.
+	#[cfg_attr(feature = "serde", serde(rename = "current_spectators"))]
+	pub spectator_count: usize,
+
+	/// Who won which round and how, ordered by round.
+	#[cfg_attr(feature = "serde", serde(default, with = "round_win::history"))]
+	pub round_wins: Vec<RoundWin>,
+
+	/// How many matches are required until a team wins.
+	#[cfg_attr(feature = "serde", serde(rename = "num_matches_to_win_series"))]
+	pub matches_to_win: usize,
+
+	/// The amount of souvenir drops on this map.
+	pub souvenirs_total: usize,
+
+	/// The Terrorist's current stats.
+	#[cfg_attr(feature = "serde", serde(rename = "team_t"))]
+	pub t_stats: GameStats,
+
+	/// The Counter-Terrorist's current stats.
+	#[cfg_attr(feature = "serde", serde(rename = "team_ct"))]
+	pub ct_stats: GameStats,
+}
+
+string_enum! {
+	/// The game mode being played.
+	///
+	/// The strings are the internal mode names CS:GO reports, which don't always match the names
+	/// shown in the UI. `Type` and `Mode` are the values of the `game_type` and `game_mode`
+	/// convars:
+	///
+	/// | Variant                                | Type | Mode | String               |
+	/// | -------------------------------------- | ---- | ---- | -------------------- |
+	/// | [`Casual`](Self::Casual)               | 0    | 0    | `casual`             |
+	/// | [`Competitive`](Self::Competitive)     | 0    | 1    | `competitive`        |
+	/// | [`Wingman`](Self::Wingman)             | 0    | 2    | `scrimcomp2v2`       |
+	/// | [`WeaponsExpert`](Self::WeaponsExpert) | 0    | 3    | `scrimcomp5v5`       |
+	/// | [`ArmsRace`](Self::ArmsRace)           | 1    | 0    | `gungameprogressive` |
+	/// | [`Demolition`](Self::Demolition)       | 1    | 1    | `gungametrbomb`      |
+	/// | [`Deathmatch`](Self::Deathmatch)       | 1    | 2    | `deathmatch`         |
+	/// | [`Training`](Self::Training)           | 2    | 0    | `training`           |
+	/// | [`Custom`](Self::Custom)               | 3    | 0    | `custom`             |
+	/// | [`Guardian`](Self::Guardian)           | 4    | 0    | `cooperative`        |
+	/// | [`CoopMission`](Self::CoopMission)     | 4    | 1    | `coopmission`        |
+	/// | [`Skirmish`](Self::Skirmish)           | 5    | 0    | `skirmish`           |
+	/// | [`Survival`](Self::Survival)           | 6    | 0    | `survival`           |
+	/// | [`Retakes`](Self::Retakes)             | -    | -    | `retakes`            |
+	///
+	/// Community servers (surf, KZ, bhop, ...) usually run `custom` or `casual`. Anything else ends
+	/// up as [`Unknown`](Self::Unknown).
+	pub enum GameMode {
+		/// Casual.
+		Casual = "casual",
+
+		/// Competitive.
+		Competitive = "competitive",
+
+		/// Wingman (2v2 competitive).
+		Wingman = "scrimcomp2v2",
+
+		/// Weapons Expert (5v5 competitive without re-buying weapons).
+		WeaponsExpert = "scrimcomp5v5",
+
+		/// Arms Race.
+		ArmsRace = "gungameprogressive",
+
+		/// Demolition.
+		Demolition = "gungametrbomb",
+
+		/// Deathmatch.
+		Deathmatch = "deathmatch",
+
+		/// The weapons course / training map.
+		Training = "training",
+
+		/// Custom game mode, used by most community servers.
+		Custom = "custom",
+
+		/// Guardian (co-op against bots).
+		Guardian = "cooperative",
+
+		/// Co-op Strike missions.
+		CoopMission = "coopmission",
+
+		/// War Games (Flying Scoutsman, Retakes, Trigger Discipline, ...).
+		Skirmish = "skirmish",
+
+		/// Danger Zone.
+		Survival = "survival",
+
+		/// Retakes.
+		Retakes = "retakes",
+	}
+}
+
+string_enum! {
+	#[allow(missing_docs)]
+	pub enum GamePhase {
+		Warmup = "warmup",
+		Live = "live",
+		Halftime = "intermission",
+		GameOver = "gameover",
+	}
+}
+
+/// Stats about the current game.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameStats {
+	/// Team name.
+	pub name: Option<String>,
+
+	#[allow(missing_docs)] // No idea what this is.
+	pub flag: Option<String>,
+
+	/// The amount of won rounds.
+	pub score: usize,
+
+	/// The amount of lost rounds in a row.
+	#[cfg_attr(feature = "serde", serde(rename = "consecutive_round_losses"))]
+	pub loss_streak: usize,
+
+	/// The amount of timeouts remaining.
+	#[cfg_attr(feature = "serde", serde(rename = "timeouts_remaining"))]
+	pub timeouts: usize,
+
+	/// The amount of wins this series.
+	#[cfg_attr(feature = "serde", serde(rename = "matches_won_this_series"))]
+	pub wins: usize,
+}
+
+/// Information about the current game round.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round {
+	/// The current phase of the round.
+	pub phase: RoundPhase,
+
+	/// The state of the bomb.
+	#[cfg_attr(feature = "serde", serde(rename = "bomb"))]
+	pub bomb_state: Option<BombState>,
+
+	/// The winner of this round.
+	#[cfg_attr(feature = "serde", serde(rename = "win_team"))]
+	pub winner: Option<Team>,
+}
+
+string_enum! {
+	/// The phase of a round.
+	#[allow(missing_docs)]
+	pub enum RoundPhase {
+		FreezeTime = "freezetime",
+		Live = "live",
+		Over = "over",
+	}
+}
+
+string_enum! {
+	/// The state of the bomb.
+	#[allow(missing_docs)]
+	pub enum BombState {
+		Planted = "planted",
+		Defused = "defused",
+		Exploded = "exploded",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unknown_values_are_preserved() {
+		let mode = GameMode::from("surf_timer");
+
+		assert_eq!(mode, GameMode::Unknown(String::from("surf_timer")));
+		assert!(!mode.is_known());
+		assert_eq!(mode.as_str(), "surf_timer");
+		assert_eq!(mode.to_string(), "surf_timer");
+
+		assert_eq!(GameMode::from(String::from("casual")), GameMode::Casual);
+		assert_eq!("gameover".parse::<GamePhase>(), Ok(GamePhase::GameOver));
+	}
+
+	#[test]
+	fn game_modes() {
+		let modes = [
+			("casual", GameMode::Casual),
+			("competitive", GameMode::Competitive),
+			("scrimcomp2v2", GameMode::Wingman),
+			("scrimcomp5v5", GameMode::WeaponsExpert),
+			("gungameprogressive", GameMode::ArmsRace),
+			("gungametrbomb", GameMode::Demolition),
+			("deathmatch", GameMode::Deathmatch),
+			("training", GameMode::Training),
+			("custom", GameMode::Custom),
+			("cooperative", GameMode::Guardian),
+			("coopmission", GameMode::CoopMission),
+			("skirmish", GameMode::Skirmish),
+			("survival", GameMode::Survival),
+			("retakes", GameMode::Retakes),
+		];
+
+		for (value, mode) in modes {
+			assert_eq!(GameMode::from(value), mode);
+			assert_eq!(mode.as_str(), value);
+			assert!(mode.is_known());
+		}
+
+		// The variant used to be misspelled as `Competetive`, the wire value never was.
+		assert_eq!(GameMode::from("competetive"), GameMode::Unknown(String::from("competetive")));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		use serde_json::json;
+
+		for (mode, value) in [
+			(GameMode::Competitive, json!("competitive")),
+			(GameMode::Unknown(String::from("surf_timer")), json!("surf_timer")),
+		] {
+			assert_eq!(serde_json::to_value(&mode).expect("serializable"), value);
+			assert_eq!(serde_json::from_value::<GameMode>(value).expect("valid mode"), mode);
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn game_keys_are_parsed() {
+		use serde_json::json;
+
+		let round = serde_json::from_value::<Round>(json!({
+			"phase": "over",
+			"bomb": "exploded",
+			"win_team": "T",
+		}))
+		.expect("valid round");
+
+		assert_eq!(round.bomb_state, Some(BombState::Exploded));
+		assert_eq!(round.winner, Some(Team::T));
+
+		let mut state = test_util::player_state(100);
+		state["round_totaldmg"] = json!(142);
+
+		let state = serde_json::from_value::<PlayerState>(state).expect("valid state");
+		assert_eq!(state.round_damage, Some(142));
+	}
+}
@@ -0,0 +1,335 @@
+//! Round win history.
+
+use {
+	super::{Map, Team},
+	std::collections::HashMap,
+};
+
+/// A single entry of [`Map::round_wins`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundWin {
+	/// The round number, starting at 1.
+	pub round: u64,
+
+	/// The team that won the round.
+	pub team: Team,
+
+	/// How the round was won.
+	pub condition: WinCondition,
+}
+
+impl RoundWin {
+	/// Parses a round win as reported by CS:GO (e.g. `ct_win_elimination`).
+	pub fn parse(round: u64, value: &str) -> Self {
+		let Some((team, condition)) = value.split_once("_win_") else {
+			return Self {
+				round,
+				team: Team::Unknown(value.to_owned()),
+				condition: WinCondition::Unknown(value.to_owned()),
+			};
+		};
+
+		Self {
+			round,
+			team: Team::from(team.to_uppercase()),
+			condition: WinCondition::from(condition),
+		}
+	}
+
+	/// The string CS:GO uses to represent this round win.
+	pub fn to_gsi_string(&self) -> String {
+		match (&self.team, &self.condition) {
+			(Team::Unknown(team), WinCondition::Unknown(condition)) if team == condition => {
+				team.clone()
+			}
+			(team, condition) => format!("{}_win_{}", team.as_str().to_lowercase(), condition),
+		}
+	}
+}
+
+string_enum! {
+	/// The ways a round can be won.
+	pub enum WinCondition {
+		/// All players of the other team died.
+		Elimination = "elimination",
+
+		/// The bomb exploded.
+		Bomb = "bomb",
+
+		/// The bomb was defused.
+		Defuse = "defuse",
+
+		/// The round timer ran out.
+		Time = "time",
+
+		/// The hostages were rescued.
+		Rescue = "rescue",
+	}
+}
+
+/// The round structure of a match.
+///
+/// The defaults match Valve's competitive settings (`mp_maxrounds 30` and
+/// `mp_overtime_maxrounds 6`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchFormat {
+	/// The amount of rounds played in regulation.
+	pub max_rounds: u64,
+
+	/// The amount of rounds played in each overtime.
+	pub overtime_max_rounds: u64,
+}
+
+impl Default for MatchFormat {
+	fn default() -> Self {
+		Self::COMPETITIVE
+	}
+}
+
+impl MatchFormat {
+	/// Competitive (MR15 with MR3 overtimes).
+	pub const COMPETITIVE: Self = Self { max_rounds: 30, overtime_max_rounds: 6 };
+	/// Wingman (MR8 with MR3 overtimes).
+	pub const WINGMAN: Self = Self { max_rounds: 16, overtime_max_rounds: 6 };
+
+	/// The [`Period`] the given `round` (starting at 1) is played in.
+	pub fn period(&self, round: u64) -> Period {
+		let round = round.saturating_sub(1);
+
+		if round < self.max_rounds {
+			let half = if round < self.max_rounds / 2 { 1 } else { 2 };
+			return Period::Regulation { half };
+		}
+
+		let overtime_max_rounds = self.overtime_max_rounds.max(1);
+		let overtime_round = round - self.max_rounds;
+		let number = overtime_round / overtime_max_rounds + 1;
+		let half =
+			if overtime_round % overtime_max_rounds < overtime_max_rounds / 2 { 1 } else { 2 };
+
+		Period::Overtime { number, half }
+	}
+
+	/// Whether teams play on the opposite side of the one they started the match on during the
+	/// given `round` (starting at 1).
+	///
+	/// Teams switch sides at halftime and at the half of every overtime. Every overtime starts
+	/// with teams on the side they finished the previous period on.
+	pub fn sides_switched(&self, round: u64) -> bool {
+		match self.period(round) {
+			Period::Regulation { half } => half == 2,
+			Period::Overtime { number, half } => (number % 2 == 1) == (half == 1),
+		}
+	}
+}
+
+/// A section of a match in which teams play on the same side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Period {
+	/// The first or second half of regulation.
+	Regulation {
+		/// `1` or `2`.
+		half: u8,
+	},
+
+	/// The first or second half of an overtime.
+	Overtime {
+		/// The number of the overtime, starting at 1.
+		number: u64,
+
+		/// `1` or `2`.
+		half: u8,
+	},
+}
+
+impl Map {
+	/// The round wins of the two halves of regulation.
+	pub fn halves(&self, format: &MatchFormat) -> [&[RoundWin]; 2] {
+		let half = format.max_rounds / 2;
+		[self.round_wins_between(1, half), self.round_wins_between(half + 1, format.max_rounds)]
+	}
+
+	/// The round wins of every overtime that has been played so far.
+	pub fn overtimes(&self, format: &MatchFormat) -> Vec<&[RoundWin]> {
+		let overtime_max_rounds = format.overtime_max_rounds.max(1);
+		let last_round = self
+			.round_wins
+			.last()
+			.map_or(0, |round_win| round_win.round);
+
+		let mut overtimes = Vec::new();
+		let mut start = format.max_rounds + 1;
+
+		while start <= last_round {
+			let end = start + overtime_max_rounds - 1;
+			overtimes.push(self.round_wins_between(start, end));
+			start = end + 1;
+		}
+
+		overtimes
+	}
+
+	/// How often `team` won a round by each [`WinCondition`].
+	pub fn win_counts(&self, team: &Team) -> HashMap<WinCondition, usize> {
+		self.round_wins
+			.iter()
+			.filter(|round_win| &round_win.team == team)
+			.fold(HashMap::new(), |mut counts, round_win| {
+				*counts
+					.entry(round_win.condition.clone())
+					.or_default() += 1;
+				counts
+			})
+	}
+
+	/// The round wins between `first` and `last` (both inclusive).
+	fn round_wins_between(&self, first: u64, last: u64) -> &[RoundWin] {
+		let start = self
+			.round_wins
+			.partition_point(|round_win| round_win.round < first);
+		let end = self
+			.round_wins
+			.partition_point(|round_win| round_win.round <= last);
+
+		&self.round_wins[start..end.max(start)]
+	}
+}
+
+/// (De)serializes [`Map::round_wins`].
+///
+/// CS:GO sends round wins as an object keyed by the round number. They are stored as a [`Vec`]
+/// ordered by round instead.
+#[cfg(feature = "serde")]
+pub(crate) mod history {
+	use {
+		super::RoundWin,
+		serde::{ser::SerializeMap, Deserialize, Deserializer, Serializer},
+		std::collections::HashMap,
+	};
+
+	pub fn serialize<S>(round_wins: &[RoundWin], serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer, {
+		let mut map = serializer.serialize_map(Some(round_wins.len()))?;
+
+		for round_win in round_wins {
+			map.serialize_entry(&round_win.round.to_string(), &round_win.to_gsi_string())?;
+		}
+
+		map.end()
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<RoundWin>, D::Error>
+	where
+		D: Deserializer<'de>, {
+		let mut round_wins = HashMap::<u64, String>::deserialize(deserializer)?
+			.into_iter()
+			.map(|(round, value)| RoundWin::parse(round, &value))
+			.collect::<Vec<_>>();
+
+		round_wins.sort_by_key(|round_win| round_win.round);
+
+		Ok(round_wins)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_win_round_trips() {
+		for value in
+			["ct_win_elimination", "t_win_bomb", "ct_win_defuse", "ct_win_time", "ct_win_rescue"]
+		{
+			assert_eq!(RoundWin::parse(1, value).to_gsi_string(), value);
+		}
+	}
+
+	#[test]
+	fn round_win_is_parsed() {
+		let round_win = RoundWin::parse(7, "t_win_bomb");
+
+		assert_eq!(round_win.round, 7);
+		assert_eq!(round_win.team, Team::T);
+		assert_eq!(round_win.condition, WinCondition::Bomb);
+	}
+
+	#[test]
+	fn unknown_round_win_round_trips() {
+		let round_win = RoundWin::parse(1, "draw");
+
+		assert_eq!(round_win.team, Team::Unknown(String::from("draw")));
+		assert_eq!(round_win.condition, WinCondition::Unknown(String::from("draw")));
+		assert_eq!(round_win.to_gsi_string(), "draw");
+
+		let round_win = RoundWin::parse(1, "ct_win_surrender");
+
+		assert_eq!(round_win.team, Team::CT);
+		assert_eq!(round_win.condition, WinCondition::Unknown(String::from("surrender")));
+		assert_eq!(round_win.to_gsi_string(), "ct_win_surrender");
+	}
+
+	#[test]
+	fn periods() {
+		let format = MatchFormat::COMPETITIVE;
+
+		assert_eq!(format.period(1), Period::Regulation { half: 1 });
+		assert_eq!(format.period(15), Period::Regulation { half: 1 });
+		assert_eq!(format.period(16), Period::Regulation { half: 2 });
+		assert_eq!(format.period(30), Period::Regulation { half: 2 });
+		assert_eq!(format.period(31), Period::Overtime { number: 1, half: 1 });
+		assert_eq!(format.period(34), Period::Overtime { number: 1, half: 2 });
+		assert_eq!(format.period(37), Period::Overtime { number: 2, half: 1 });
+	}
+
+	#[test]
+	fn sides_switched() {
+		let format = MatchFormat::COMPETITIVE;
+		let switched = (1..=42)
+			.filter(|&round| format.sides_switched(round))
+			.collect::<Vec<_>>();
+
+		// Second half, first half of the first overtime and second half of the second overtime.
+		let expected = (16..=33).chain(40..=42).collect::<Vec<_>>();
+
+		assert_eq!(switched, expected);
+	}
+
+	#[test]
+	fn sides_switched_in_wingman() {
+		let format = MatchFormat::WINGMAN;
+
+		assert!(!format.sides_switched(8));
+		assert!(format.sides_switched(9));
+		assert!(format.sides_switched(17));
+		assert!(!format.sides_switched(20));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn history_is_sorted_by_round() {
+		#[derive(Debug, serde::Serialize, serde::Deserialize)]
+		struct Wrapper {
+			#[serde(with = "history")]
+			round_wins: Vec<RoundWin>,
+		}
+
+		let json = serde_json::json!({
+			"round_wins": { "10": "t_win_time", "2": "ct_win_defuse", "1": "t_win_elimination" },
+		});
+
+		let wrapper = serde_json::from_value::<Wrapper>(json.clone()).expect("valid history");
+		let rounds = wrapper
+			.round_wins
+			.iter()
+			.map(|round_win| round_win.round)
+			.collect::<Vec<_>>();
+
+		assert_eq!(rounds, [1, 2, 10]);
+		assert_eq!(serde_json::to_value(&wrapper).expect("serializable"), json);
+	}
+}
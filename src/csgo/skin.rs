@@ -0,0 +1,258 @@
+//! Weapon skins ("paint kits").
+
+use std::collections::HashMap;
+
+/// The skin applied to a weapon, as reported by CS:GO (e.g. `cu_ak47_asiimov`).
+///
+/// Skin names are usually prefixed with a code for their [`Finish`]. Weapons without a skin
+/// report `default`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Skin(String);
+
+impl Skin {
+	/// The paintkit name CS:GO uses for weapons without a skin.
+	pub const DEFAULT: &'static str = "default";
+
+	/// Constructs a new [`Skin`] from a raw paintkit name.
+	pub fn new(paintkit: impl Into<String>) -> Self {
+		Self(paintkit.into())
+	}
+
+	/// The raw paintkit name.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// Whether the weapon has no skin applied.
+	pub fn is_default(&self) -> bool {
+		matches!(self.0.as_str(), "" | Self::DEFAULT | "vanilla")
+	}
+
+	/// The finish prefix of the paintkit, if it has a known one.
+	pub fn finish(&self) -> Option<Finish> {
+		let (prefix, _) = self.0.split_once('_')?;
+		let finish = Finish::from(prefix);
+		finish.is_known().then_some(finish)
+	}
+
+	/// The paintkit name without its [`Finish`] prefix.
+	pub fn name(&self) -> &str {
+		match self.finish() {
+			Some(finish) => &self.0[finish.as_str().len() + 1..],
+			None => &self.0,
+		}
+	}
+
+	/// Looks up the human readable name of this skin in the given `table`.
+	pub fn display_name<'table>(&self, table: &'table SkinTable) -> Option<&'table str> {
+		table.get(self)
+	}
+}
+
+impl From<String> for Skin {
+	fn from(paintkit: String) -> Self {
+		Self(paintkit)
+	}
+}
+
+impl From<&str> for Skin {
+	fn from(paintkit: &str) -> Self {
+		Self(paintkit.to_owned())
+	}
+}
+
+impl std::fmt::Display for Skin {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+string_enum! {
+	/// The finish style of a skin, encoded as the prefix of its paintkit name.
+	pub enum Finish {
+		/// Anodized Airbrushed.
+		AnodizedAirbrushed = "aa",
+
+		/// Anodized Multicolored.
+		AnodizedMulticolored = "am",
+
+		/// Anodized.
+		Anodized = "an",
+
+		/// Patina.
+		Patina = "aq",
+
+		/// Custom Paint Job.
+		CustomPaint = "cu",
+
+		/// Gunsmith.
+		Gunsmith = "gs",
+
+		/// Hydrographic.
+		Hydrographic = "hy",
+
+		/// Solid Color.
+		SolidColor = "so",
+
+		/// Spray-Paint.
+		SprayPaint = "sp",
+	}
+}
+
+/// Lookup table for human readable skin names.
+///
+/// The table can either be filled manually or parsed from an `items_game`-style `paint_kits`
+/// section (see [`SkinTable::from_vdf`]).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SkinTable {
+	names: HashMap<String, String>,
+}
+
+impl SkinTable {
+	/// Constructs an empty [`SkinTable`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses a `paint_kits` section in the same format as CS:GO's `items_game.txt`.
+	///
+	/// Every entry needs a `name` (the paintkit name) and a `description_tag`; entries missing
+	/// either are skipped. Unlike in the game files, the `description_tag` has to contain the
+	/// already localized name of the skin. Localization tokens such as
+	/// `#PaintKit_cu_ak47_asiimov_Tag` are not resolved and would be stored as they are.
+	///
+	/// ```not_rust
+	/// "paint_kits"
+	/// {
+	///     "279"
+	///     {
+	///         "name"             "cu_ak47_asiimov"
+	///         "description_tag"  "Asiimov"
+	///     }
+	/// }
+	/// ```
+	#[cfg(feature = "serde")]
+	pub fn from_vdf(vdf: &str) -> crate::Result<Self> {
+		#[derive(serde::Deserialize)]
+		#[serde(rename = "paint_kits")]
+		struct PaintKits(HashMap<String, HashMap<String, String>>);
+
+		let PaintKits(paint_kits) =
+			vdf_serde::from_str(vdf).map_err(|err| crate::Error::ParseVdf(err.to_string()))?;
+
+		Ok(paint_kits
+			.into_values()
+			.filter_map(|mut paint_kit| {
+				let name = paint_kit.remove("name")?;
+				let display_name = paint_kit.remove("description_tag")?;
+				Some((name, display_name))
+			})
+			.collect())
+	}
+
+	/// Adds a skin to the table, returning the name that was previously stored for it.
+	pub fn insert(
+		&mut self,
+		paintkit: impl Into<String>,
+		display_name: impl Into<String>,
+	) -> Option<String> {
+		self.names
+			.insert(paintkit.into(), display_name.into())
+	}
+
+	/// Looks up the human readable name of a skin.
+	pub fn get(&self, skin: &Skin) -> Option<&str> {
+		self.names.get(skin.as_str()).map(String::as_str)
+	}
+
+	/// The amount of skins in the table.
+	pub fn len(&self) -> usize {
+		self.names.len()
+	}
+
+	/// Whether the table is empty.
+	pub fn is_empty(&self) -> bool {
+		self.names.is_empty()
+	}
+}
+
+impl<K, V> FromIterator<(K, V)> for SkinTable
+where
+	K: Into<String>,
+	V: Into<String>,
+{
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+		Self {
+			names: iter
+				.into_iter()
+				.map(|(paintkit, display_name)| (paintkit.into(), display_name.into()))
+				.collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finish_and_name() {
+		let asiimov = Skin::new("cu_ak47_asiimov");
+		assert_eq!(asiimov.finish(), Some(Finish::CustomPaint));
+		assert_eq!(asiimov.name(), "ak47_asiimov");
+		assert!(!asiimov.is_default());
+
+		let unknown = Skin::new("xx_something");
+		assert_eq!(unknown.finish(), None);
+		assert_eq!(unknown.name(), "xx_something");
+
+		let unprefixed = Skin::new("asiimov");
+		assert_eq!(unprefixed.finish(), None);
+		assert_eq!(unprefixed.name(), "asiimov");
+	}
+
+	#[test]
+	fn default_skins() {
+		for paintkit in ["", Skin::DEFAULT, "vanilla"] {
+			let skin = Skin::new(paintkit);
+			assert!(skin.is_default(), "{paintkit:?} should be a default skin");
+			assert_eq!(skin.finish(), None);
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn from_vdf() {
+		let table = SkinTable::from_vdf(
+			r#"
+			"paint_kits"
+			{
+				"279"
+				{
+					"name"             "cu_ak47_asiimov"
+					"description_tag"  "Asiimov"
+				}
+				"44"
+				{
+					"name"             "hy_ddpat"
+					"description_tag"  "Urban DDPAT"
+				}
+				"0"
+				{
+					"name"             "default"
+				}
+			}
+			"#,
+		)
+		.expect("valid paint kits");
+
+		assert_eq!(table.len(), 2);
+		assert_eq!(Skin::new("cu_ak47_asiimov").display_name(&table), Some("Asiimov"));
+		assert_eq!(table.get(&Skin::new("hy_ddpat")), Some("Urban DDPAT"));
+		assert_eq!(table.get(&Skin::new(Skin::DEFAULT)), None);
+
+		assert!(SkinTable::from_vdf("\"paint_kits\" {").is_err());
+	}
+}
@@ -0,0 +1,74 @@
+//! JSON fixtures shared by unit tests.
+
+// Not every feature combination has tests using every fixture.
+#![allow(dead_code)]
+
+use {
+	super::Event,
+	serde_json::{json, Value},
+};
+
+/// The `SteamID64` of the player sending events.
+pub(crate) const STEAM_ID: &str = "76561198000000001";
+
+/// Parses an event from `value`.
+pub(crate) fn from_json(value: Value) -> Event {
+	serde_json::from_value(value).expect("valid event")
+}
+
+/// An event sent by [`STEAM_ID`] at `timestamp`, without any other sections.
+pub(crate) fn event(timestamp: u64) -> Event {
+	from_json(json!({ "provider": provider(timestamp) }))
+}
+
+/// The `provider` section of an event sent by [`STEAM_ID`] at `timestamp`.
+pub(crate) fn provider(timestamp: u64) -> Value {
+	json!({
+		"name": "Counter-Strike: Global Offensive",
+		"appid": 730,
+		"version": 13_881,
+		"steamid": STEAM_ID,
+		"timestamp": timestamp,
+	})
+}
+
+/// The `state` of a player with `health` who has made a kill this round.
+pub(crate) fn player_state(health: u8) -> Value {
+	json!({
+		"health": health,
+		"armor": 0,
+		"helmet": false,
+		"flashed": 0,
+		"smoked": 0,
+		"burning": 0,
+		"money": 800,
+		"round_kills": 1,
+		"round_killhs": 1,
+		"equip_value": 200,
+	})
+}
+
+/// The `map` section of a live match on `name` after `round` rounds.
+pub(crate) fn map(name: &str, round: u8) -> Value {
+	json!({
+		"name": name,
+		"mode": "competitive",
+		"phase": "live",
+		"round": round,
+		"current_spectators": 0,
+		"num_matches_to_win_series": 0,
+		"souvenirs_total": 0,
+		"team_t": team_stats(0),
+		"team_ct": team_stats(0),
+	})
+}
+
+/// The stats of a team in the `map` section that has won `score` rounds.
+pub(crate) fn team_stats(score: usize) -> Value {
+	json!({
+		"score": score,
+		"consecutive_round_losses": 0,
+		"timeouts_remaining": 1,
+		"matches_won_this_series": 0,
+	})
+}
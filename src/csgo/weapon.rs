@@ -0,0 +1,374 @@
+//! Weapon related types.
+
+use super::{Skin, Team};
+
+/// Information about a weapon.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Weapon {
+	/// The weapon's identifier.
+	pub name: WeaponId,
+
+	/// The skin of the weapon.
+	#[cfg_attr(feature = "serde", serde(rename = "paintkit"))]
+	pub skin: Skin,
+
+	/// The type of the weapon.
+	#[cfg_attr(feature = "serde", serde(rename = "type"))]
+	pub weapon_type: WeaponType,
+
+	/// The state of the weapon.
+	#[cfg_attr(feature = "serde", serde(rename = "state"))]
+	pub weapon_state: WeaponState,
+
+	/// The amount of ammo left.
+	pub ammo_clip: Option<usize>,
+
+	/// The maximum amount of ammo this weapon can hold.
+	#[cfg_attr(feature = "serde", serde(rename = "max_ammo"))]
+	pub ammo_max: Option<usize>,
+
+	/// The amount of ammo left in reserve.
+	pub ammo_reserve: Option<usize>,
+}
+
+string_enum! {
+	/// The different types of weapons.
+	#[allow(missing_docs, clippy::upper_case_acronyms)]
+	pub enum WeaponType {
+		Knife = "Knife",
+		Pistol = "Pistol",
+		SMG = "Submachine Gun",
+		MachineGun = "Machine Gun",
+		Rifle = "Rifle",
+		SniperRifle = "SniperRifle",
+		Shotgun = "Shotgun",
+		StackableItem = "StackableItem",
+		Grenade = "Grenade",
+		C4 = "C4",
+		Taser = "Taser",
+		Tablet = "Tablet",
+		Melee = "Melee",
+		Fists = "Fists",
+		BreachCharge = "Breach Charge",
+		BumpMine = "Bump Mine",
+	}
+}
+
+string_enum! {
+	/// The 3 states a weapon can be in.
+	#[allow(missing_docs)]
+	pub enum WeaponState {
+		Holstered = "holstered",
+		Active = "active",
+		Reloading = "reloading",
+	}
+}
+
+/// Declares [`WeaponId`] together with the static information about every item.
+///
+/// Each row is `Variant("internal name", "display name", category, price, magazine, team)`.
+macro_rules! weapons {
+	($(
+		$variant:ident(
+			$id:literal,
+			$name:literal,
+			$category:ident,
+			$price:expr,
+			$magazine:expr,
+			$team:expr $(,)?
+		),
+	)*) => {
+		string_enum! {
+			/// Every item a player can hold, identified by the internal name CS:GO reports for it
+			/// (e.g. `weapon_ak47`).
+			pub enum WeaponId {
+				$(
+					#[doc = $name]
+					$variant = $id,
+				)*
+			}
+		}
+
+		impl WeaponId {
+			/// Every known item.
+			pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+			/// The name of the item as it is displayed in-game.
+			pub const fn display_name(&self) -> Option<&'static str> {
+				match self {
+					$(Self::$variant => Some($name),)*
+					Self::Unknown(_) => None,
+				}
+			}
+
+			/// The category of the item.
+			pub const fn category(&self) -> Option<WeaponType> {
+				match self {
+					$(Self::$variant => Some(WeaponType::$category),)*
+					Self::Unknown(_) => None,
+				}
+			}
+
+			/// The price of the item in the buy menu.
+			///
+			/// Is [`None`] for items that can't be bought.
+			pub const fn price(&self) -> Option<u16> {
+				match self {
+					$(Self::$variant => $price,)*
+					Self::Unknown(_) => None,
+				}
+			}
+
+			/// How many bullets fit into a single magazine.
+			///
+			/// Is [`None`] for items that don't use ammunition.
+			pub const fn magazine_size(&self) -> Option<u16> {
+				match self {
+					$(Self::$variant => $magazine,)*
+					Self::Unknown(_) => None,
+				}
+			}
+
+			/// The only team that can buy or spawn with this item.
+			///
+			/// Is [`None`] if both teams have access to it, or if the item is unknown.
+			pub const fn team(&self) -> Option<Team> {
+				match self {
+					$(Self::$variant => $team,)*
+					Self::Unknown(_) => None,
+				}
+			}
+		}
+	};
+}
+
+weapons! {
+	// Pistols
+	Deagle("weapon_deagle", "Desert Eagle", Pistol, Some(700), Some(7), None),
+	DualBerettas("weapon_elite", "Dual Berettas", Pistol, Some(300), Some(30), None),
+	FiveSeven("weapon_fiveseven", "Five-SeveN", Pistol, Some(500), Some(20), Some(Team::CT)),
+	Glock("weapon_glock", "Glock-18", Pistol, Some(200), Some(20), Some(Team::T)),
+	P2000("weapon_hkp2000", "P2000", Pistol, Some(200), Some(13), Some(Team::CT)),
+	UspS("weapon_usp_silencer", "USP-S", Pistol, Some(200), Some(12), Some(Team::CT)),
+	P250("weapon_p250", "P250", Pistol, Some(300), Some(13), None),
+	Cz75Auto("weapon_cz75a", "CZ75-Auto", Pistol, Some(500), Some(12), None),
+	Tec9("weapon_tec9", "Tec-9", Pistol, Some(500), Some(18), Some(Team::T)),
+	Revolver("weapon_revolver", "R8 Revolver", Pistol, Some(600), Some(8), None),
+
+	// SMGs
+	Mac10("weapon_mac10", "MAC-10", SMG, Some(1050), Some(30), Some(Team::T)),
+	Mp9("weapon_mp9", "MP9", SMG, Some(1250), Some(30), Some(Team::CT)),
+	Mp7("weapon_mp7", "MP7", SMG, Some(1500), Some(30), None),
+	Mp5Sd("weapon_mp5sd", "MP5-SD", SMG, Some(1500), Some(30), None),
+	Ump45("weapon_ump45", "UMP-45", SMG, Some(1200), Some(25), None),
+	P90("weapon_p90", "P90", SMG, Some(2350), Some(50), None),
+	PpBizon("weapon_bizon", "PP-Bizon", SMG, Some(1400), Some(64), None),
+
+	// Rifles
+	Ak47("weapon_ak47", "AK-47", Rifle, Some(2700), Some(30), Some(Team::T)),
+	Aug("weapon_aug", "AUG", Rifle, Some(3300), Some(30), Some(Team::CT)),
+	Famas("weapon_famas", "FAMAS", Rifle, Some(2050), Some(25), Some(Team::CT)),
+	GalilAr("weapon_galilar", "Galil AR", Rifle, Some(1800), Some(35), Some(Team::T)),
+	M4A4("weapon_m4a1", "M4A4", Rifle, Some(3100), Some(30), Some(Team::CT)),
+	M4A1S("weapon_m4a1_silencer", "M4A1-S", Rifle, Some(2900), Some(20), Some(Team::CT)),
+	Sg553("weapon_sg556", "SG 553", Rifle, Some(3000), Some(30), Some(Team::T)),
+
+	// Sniper rifles
+	Awp("weapon_awp", "AWP", SniperRifle, Some(4750), Some(5), None),
+	G3SG1("weapon_g3sg1", "G3SG1", SniperRifle, Some(5000), Some(20), Some(Team::T)),
+	Scar20("weapon_scar20", "SCAR-20", SniperRifle, Some(5000), Some(20), Some(Team::CT)),
+	Ssg08("weapon_ssg08", "SSG 08", SniperRifle, Some(1700), Some(10), None),
+
+	// Shotguns
+	Mag7("weapon_mag7", "MAG-7", Shotgun, Some(1300), Some(5), Some(Team::CT)),
+	Nova("weapon_nova", "Nova", Shotgun, Some(1050), Some(8), None),
+	SawedOff("weapon_sawedoff", "Sawed-Off", Shotgun, Some(1100), Some(7), Some(Team::T)),
+	Xm1014("weapon_xm1014", "XM1014", Shotgun, Some(2000), Some(7), None),
+
+	// Machine guns
+	M249("weapon_m249", "M249", MachineGun, Some(5200), Some(100), None),
+	Negev("weapon_negev", "Negev", MachineGun, Some(1700), Some(150), None),
+
+	// Grenades
+	Decoy("weapon_decoy", "Decoy Grenade", Grenade, Some(50), None, None),
+	Flashbang("weapon_flashbang", "Flashbang", Grenade, Some(200), None, None),
+	HeGrenade("weapon_hegrenade", "High Explosive Grenade", Grenade, Some(300), None, None),
+	Incendiary("weapon_incgrenade", "Incendiary Grenade", Grenade, Some(600), None, Some(Team::CT)),
+	Molotov("weapon_molotov", "Molotov", Grenade, Some(400), None, Some(Team::T)),
+	SmokeGrenade("weapon_smokegrenade", "Smoke Grenade", Grenade, Some(300), None, None),
+	TaGrenade("weapon_tagrenade", "Tactical Awareness Grenade", Grenade, None, None, None),
+	Snowball("weapon_snowball", "Snowball", Grenade, None, None, None),
+	Firebomb("weapon_firebomb", "Fire Bomb", Grenade, None, None, None),
+	Diversion("weapon_diversion", "Diversion Device", Grenade, None, None, None),
+	FragGrenade("weapon_frag_grenade", "Frag Grenade", Grenade, None, None, None),
+
+	// Equipment
+	C4("weapon_c4", "C4 Explosive", C4, None, None, Some(Team::T)),
+	Zeus("weapon_taser", "Zeus x27", Taser, Some(200), Some(1), None),
+	Healthshot("weapon_healthshot", "Medi-Shot", StackableItem, None, None, None),
+	Tablet("weapon_tablet", "Tablet", Tablet, None, None, None),
+	BreachCharge("weapon_breachcharge", "Breach Charge", BreachCharge, None, None, None),
+	BumpMine("weapon_bumpmine", "Bump Mine", BumpMine, None, None, None),
+	Fists("weapon_fists", "Bare Hands", Fists, None, None, None),
+	Axe("weapon_axe", "Axe", Melee, None, None, None),
+	Hammer("weapon_hammer", "Hammer", Melee, None, None, None),
+	Wrench("weapon_spanner", "Wrench", Melee, None, None, None),
+
+	// Knives
+	KnifeCT("weapon_knife", "Knife", Knife, None, None, Some(Team::CT)),
+	KnifeT("weapon_knife_t", "Knife", Knife, None, None, Some(Team::T)),
+	GoldenKnife("weapon_knifegg", "Golden Knife", Knife, None, None, None),
+	Bayonet("weapon_bayonet", "Bayonet", Knife, None, None, None),
+	ClassicKnife("weapon_knife_css", "Classic Knife", Knife, None, None, None),
+	FlipKnife("weapon_knife_flip", "Flip Knife", Knife, None, None, None),
+	GutKnife("weapon_knife_gut", "Gut Knife", Knife, None, None, None),
+	Karambit("weapon_knife_karambit", "Karambit", Knife, None, None, None),
+	M9Bayonet("weapon_knife_m9_bayonet", "M9 Bayonet", Knife, None, None, None),
+	HuntsmanKnife("weapon_knife_tactical", "Huntsman Knife", Knife, None, None, None),
+	FalchionKnife("weapon_knife_falchion", "Falchion Knife", Knife, None, None, None),
+	BowieKnife("weapon_knife_survival_bowie", "Bowie Knife", Knife, None, None, None),
+	ButterflyKnife("weapon_knife_butterfly", "Butterfly Knife", Knife, None, None, None),
+	ShadowDaggers("weapon_knife_push", "Shadow Daggers", Knife, None, None, None),
+	ParacordKnife("weapon_knife_cord", "Paracord Knife", Knife, None, None, None),
+	SurvivalKnife("weapon_knife_canis", "Survival Knife", Knife, None, None, None),
+	UrsusKnife("weapon_knife_ursus", "Ursus Knife", Knife, None, None, None),
+	NavajaKnife("weapon_knife_gypsy_jackknife", "Navaja Knife", Knife, None, None, None),
+	NomadKnife("weapon_knife_outdoor", "Nomad Knife", Knife, None, None, None),
+	StilettoKnife("weapon_knife_stiletto", "Stiletto Knife", Knife, None, None, None),
+	TalonKnife("weapon_knife_widowmaker", "Talon Knife", Knife, None, None, None),
+	SkeletonKnife("weapon_knife_skeleton", "Skeleton Knife", Knife, None, None, None),
+	SpectralShiv("weapon_knife_ghost", "Spectral Shiv", Knife, None, None, None),
+}
+
+impl WeaponId {
+	/// Whether players on `team` can buy or spawn with this item.
+	pub fn is_available_to(&self, team: &Team) -> bool {
+		self.team()
+			.is_none_or(|restriction| &restriction == team)
+	}
+}
+
+/// (De)serializes a player's weapons.
+///
+/// CS:GO sends weapons as an object keyed by their slot (`weapon_0`, `weapon_1`, ...). They are
+/// stored as a [`Vec`] ordered by that slot instead.
+#[cfg(feature = "serde")]
+pub(crate) mod slots {
+	use {
+		super::Weapon,
+		serde::{ser::SerializeMap, Deserialize, Deserializer, Serializer},
+		std::collections::HashMap,
+	};
+
+	pub fn serialize<S>(weapons: &[Weapon], serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer, {
+		let mut map = serializer.serialize_map(Some(weapons.len()))?;
+
+		for (slot, weapon) in weapons.iter().enumerate() {
+			map.serialize_entry(&format!("weapon_{slot}"), weapon)?;
+		}
+
+		map.end()
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Weapon>, D::Error>
+	where
+		D: Deserializer<'de>, {
+		let mut weapons = HashMap::<String, Weapon>::deserialize(deserializer)?
+			.into_iter()
+			.map(|(slot, weapon)| {
+				let index = slot
+					.strip_prefix("weapon_")
+					.and_then(|index| index.parse::<usize>().ok())
+					.unwrap_or(usize::MAX);
+
+				(index, slot, weapon)
+			})
+			.collect::<Vec<_>>();
+
+		weapons.sort_by(|(a_index, a_slot, _), (b_index, b_slot, _)| {
+			a_index
+				.cmp(b_index)
+				.then_with(|| a_slot.cmp(b_slot))
+		});
+
+		Ok(weapons
+			.into_iter()
+			.map(|(_, _, weapon)| weapon)
+			.collect())
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use {super::*, serde_json::json};
+
+	#[derive(Debug, serde::Serialize, serde::Deserialize)]
+	struct Wrapper {
+		#[serde(with = "slots")]
+		weapons: Vec<Weapon>,
+	}
+
+	fn weapon(name: &str) -> serde_json::Value {
+		json!({ "name": name, "paintkit": "default", "type": "Rifle", "state": "holstered" })
+	}
+
+	fn names(wrapper: &Wrapper) -> Vec<String> {
+		wrapper
+			.weapons
+			.iter()
+			.map(|weapon| weapon.name.as_str().to_owned())
+			.collect()
+	}
+
+	#[test]
+	fn slots_are_ordered_numerically() {
+		let wrapper = serde_json::from_value::<Wrapper>(json!({
+			"weapons": {
+				"weapon_10": weapon("weapon_ak47"),
+				"weapon_2": weapon("weapon_glock"),
+				"weapon_0": weapon("weapon_knife"),
+				"weapon_1": weapon("weapon_hegrenade"),
+			},
+		}))
+		.expect("valid weapons");
+
+		assert_eq!(names(&wrapper), [
+			"weapon_knife",
+			"weapon_hegrenade",
+			"weapon_glock",
+			"weapon_ak47"
+		]);
+	}
+
+	#[test]
+	fn unknown_slots_are_ordered_last() {
+		let wrapper = serde_json::from_value::<Wrapper>(json!({
+			"weapons": {
+				"other": weapon("weapon_glock"),
+				"weapon_0": weapon("weapon_knife"),
+			},
+		}))
+		.expect("valid weapons");
+
+		assert_eq!(names(&wrapper), ["weapon_knife", "weapon_glock"]);
+	}
+
+	#[test]
+	fn slots_round_trip() {
+		let json = json!({
+			"weapons": {
+				"weapon_0": weapon("weapon_knife"),
+				"weapon_1": weapon("weapon_glock"),
+			},
+		});
+
+		let wrapper = serde_json::from_value::<Wrapper>(json).expect("valid weapons");
+		let json = serde_json::to_value(&wrapper).expect("serializable");
+		let round_tripped = serde_json::from_value::<Wrapper>(json).expect("valid weapons");
+
+		assert_eq!(round_tripped.weapons, wrapper.weapons);
+	}
+}
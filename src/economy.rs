@@ -0,0 +1,329 @@
+//! Economy tracking for the current player.
+//!
+//! [`EconomyTracker`] follows the player's money and equipment value across rounds, classifies
+//! their buys and predicts how much money they will at least have next round.
+
+use crate::csgo::{Event, RoundPhase, Team};
+
+/// The maximum amount of money a player can have.
+pub const MAX_MONEY: usize = 16000;
+
+/// The money a team gets for losing a round after `loss_streak` consecutive losses.
+///
+/// `loss_streak` is the value of [`GameStats::loss_streak`](crate::csgo::GameStats::loss_streak)
+/// *before* the round is lost.
+pub fn loss_bonus(loss_streak: usize) -> usize {
+	1400 + 500 * loss_streak.min(4)
+}
+
+/// How much a player invested into a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BuyType {
+	/// Little to no equipment, saving money for later rounds.
+	Eco,
+
+	/// Some equipment, but not enough for a full loadout.
+	Force,
+
+	/// Rifle (or better) and armor.
+	FullBuy,
+}
+
+impl BuyType {
+	/// Equipment values below this are considered an [`Eco`](Self::Eco).
+	pub const ECO_THRESHOLD: usize = 1500;
+	/// Equipment values at or above this are considered a [`FullBuy`](Self::FullBuy).
+	pub const FULL_BUY_THRESHOLD: usize = 3900;
+
+	/// Classifies a buy by the player's equipment value.
+	pub const fn classify(equipment_value: usize) -> Self {
+		if equipment_value < Self::ECO_THRESHOLD {
+			Self::Eco
+		} else if equipment_value < Self::FULL_BUY_THRESHOLD {
+			Self::Force
+		} else {
+			Self::FullBuy
+		}
+	}
+}
+
+/// The current player's economy during a single round.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundEconomy {
+	/// The round number, starting at 1.
+	pub round: u64,
+
+	/// The team the player played on.
+	pub team: Option<Team>,
+
+	/// The money the player had when the round started.
+	pub start_money: usize,
+
+	/// The money the player had at the end of the round.
+	pub end_money: usize,
+
+	/// How much money the player spent.
+	///
+	/// This is the difference between the starting money and the lowest amount of money seen
+	/// during the round, so money earned before buying is not counted.
+	pub spent: usize,
+
+	/// The highest equipment value seen during the round.
+	pub equipment_value: usize,
+
+	/// The player's team's loss streak at the start of the round.
+	pub loss_streak: usize,
+}
+
+impl RoundEconomy {
+	/// Classifies this round's buy.
+	pub const fn buy_type(&self) -> BuyType {
+		BuyType::classify(self.equipment_value)
+	}
+}
+
+/// Tracks the current player's economy across events.
+///
+/// Events for other players (e.g. while spectating someone) are ignored. All rounds are cleared
+/// once a new match starts or the map changes.
+#[derive(Debug, Default, Clone)]
+pub struct EconomyTracker {
+	/// The round that is currently being played.
+	current: Option<RoundEconomy>,
+
+	/// All finished rounds.
+	rounds: Vec<RoundEconomy>,
+
+	/// The lowest amount of money seen during the current round.
+	lowest_money: usize,
+
+	/// The round phase of the last event.
+	last_phase: Option<RoundPhase>,
+
+	/// The name of the map the tracked rounds were played on.
+	map: Option<String>,
+}
+
+impl EconomyTracker {
+	/// Constructs a new [`EconomyTracker`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Updates the tracker with a new event.
+	pub fn update(&mut self, event: &Event) {
+		let Some(player) = event.player.as_ref() else {
+			return;
+		};
+
+		if event
+			.provider
+			.as_ref()
+			.is_some_and(|provider| provider.steam_id != player.steam_id)
+		{
+			return;
+		}
+
+		let (Some(map), Some(state)) = (event.map.as_ref(), player.state.as_ref()) else {
+			return;
+		};
+
+		let phase = event.round.as_ref().map(|round| &round.phase);
+		let round = u64::from(map.round) + 1;
+
+		// A new match has started.
+		if self
+			.rounds
+			.last()
+			.is_some_and(|last| last.round >= round)
+			|| self
+				.map
+				.as_ref()
+				.is_some_and(|name| name != &map.name)
+		{
+			self.reset();
+		}
+
+		self.map.get_or_insert_with(|| map.name.clone());
+
+		// A new round has started.
+		if phase == Some(&RoundPhase::FreezeTime) && self.last_phase != Some(RoundPhase::FreezeTime)
+		{
+			if let Some(finished) = self.current.take() {
+				self.rounds.push(finished);
+			}
+		}
+
+		self.last_phase = phase.cloned();
+
+		let loss_streak = match player.team.as_ref() {
+			Some(Team::T) => map.t_stats.loss_streak,
+			Some(Team::CT) => map.ct_stats.loss_streak,
+			_ => 0,
+		};
+
+		let current = self.current.get_or_insert_with(|| {
+			self.lowest_money = state.money;
+			RoundEconomy {
+				round,
+				team: player.team.clone(),
+				start_money: state.money,
+				end_money: state.money,
+				spent: 0,
+				equipment_value: 0,
+				loss_streak,
+			}
+		});
+
+		self.lowest_money = self.lowest_money.min(state.money);
+		current.end_money = state.money;
+		current.spent = current
+			.start_money
+			.saturating_sub(self.lowest_money);
+		current.equipment_value = current.equipment_value.max(state.equipment_value);
+	}
+
+	/// The round that is currently being played.
+	pub fn current_round(&self) -> Option<&RoundEconomy> {
+		self.current.as_ref()
+	}
+
+	/// All finished rounds, in order.
+	pub fn rounds(&self) -> &[RoundEconomy] {
+		&self.rounds
+	}
+
+	/// The least amount of money the player will have next round.
+	///
+	/// This assumes the player's team loses the current round and the player doesn't spend or
+	/// earn any more money, so only the loss bonus is added to their current money. Terrorists
+	/// that survive a round lost on time don't receive the loss bonus, which is not accounted for.
+	pub fn predicted_minimum_money(&self) -> Option<usize> {
+		self.current
+			.as_ref()
+			.map(|current| (current.end_money + loss_bonus(current.loss_streak)).min(MAX_MONEY))
+	}
+
+	/// Clears all tracked rounds, e.g. because a new match started.
+	pub fn reset(&mut self) {
+		*self = Self::default();
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use {
+		super::*,
+		crate::csgo::test_util::{self, STEAM_ID},
+		serde_json::json,
+	};
+
+	/// An event of the current player on CT after `completed` rounds on `map`.
+	fn event(map: &str, completed: u8, phase: &str, money: usize, equipment_value: usize) -> Event {
+		let mut state = test_util::player_state(100);
+		state["money"] = json!(money);
+		state["equip_value"] = json!(equipment_value);
+
+		let mut map = test_util::map(map, completed);
+		map["team_ct"]["consecutive_round_losses"] = json!(2);
+
+		test_util::from_json(json!({
+			"provider": test_util::provider(0),
+			"player": {
+				"name": "player",
+				"steamid": STEAM_ID,
+				"activity": "playing",
+				"team": "CT",
+				"state": state,
+			},
+			"map": map,
+			"round": { "phase": phase },
+		}))
+	}
+
+	#[test]
+	fn buy_types() {
+		assert_eq!(BuyType::classify(0), BuyType::Eco);
+		assert_eq!(BuyType::classify(1499), BuyType::Eco);
+		assert_eq!(BuyType::classify(1500), BuyType::Force);
+		assert_eq!(BuyType::classify(3899), BuyType::Force);
+		assert_eq!(BuyType::classify(3900), BuyType::FullBuy);
+	}
+
+	#[test]
+	fn loss_bonus_is_capped() {
+		assert_eq!(loss_bonus(0), 1400);
+		assert_eq!(loss_bonus(1), 1900);
+		assert_eq!(loss_bonus(4), 3400);
+		assert_eq!(loss_bonus(5), 3400);
+		assert_eq!(loss_bonus(usize::MAX), 3400);
+	}
+
+	#[test]
+	fn rounds_are_tracked() {
+		let mut tracker = EconomyTracker::new();
+
+		tracker.update(&event("de_mirage", 3, "freezetime", 4000, 200));
+		tracker.update(&event("de_mirage", 3, "freezetime", 1000, 3000));
+
+		// A kill reward doesn't reduce what was spent.
+		tracker.update(&event("de_mirage", 3, "live", 1300, 3000));
+
+		let current = tracker.current_round().expect("round is tracked");
+		assert_eq!(current.round, 4);
+		assert_eq!(current.team, Some(Team::CT));
+		assert_eq!(current.start_money, 4000);
+		assert_eq!(current.end_money, 1300);
+		assert_eq!(current.spent, 3000);
+		assert_eq!(current.loss_streak, 2);
+		assert_eq!(current.buy_type(), BuyType::Force);
+
+		// Losing with a loss streak of 2 gives 2400.
+		assert_eq!(tracker.predicted_minimum_money(), Some(3700));
+
+		tracker.update(&event("de_mirage", 4, "freezetime", 15000, 200));
+
+		assert_eq!(tracker.rounds().len(), 1);
+		assert_eq!(tracker.rounds()[0].round, 4);
+		assert_eq!(tracker.current_round().map(|round| round.round), Some(5));
+		assert_eq!(tracker.predicted_minimum_money(), Some(MAX_MONEY));
+	}
+
+	#[test]
+	fn reset_on_new_match_or_map() {
+		let mut tracker = EconomyTracker::new();
+		let mut play = |map, completed| {
+			tracker.update(&event(map, completed, "freezetime", 4000, 200));
+			tracker.update(&event(map, completed, "live", 4000, 200));
+			tracker.rounds().len()
+		};
+
+		assert_eq!(play("de_mirage", 3), 0);
+		assert_eq!(play("de_mirage", 4), 1);
+
+		// Same map, but the round went back.
+		assert_eq!(play("de_mirage", 0), 0);
+		assert_eq!(play("de_mirage", 1), 1);
+
+		// Different map, even though the round went up.
+		assert_eq!(play("de_inferno", 5), 0);
+		assert_eq!(tracker.current_round().map(|round| round.round), Some(6));
+	}
+
+	#[test]
+	fn other_players_are_ignored() {
+		let mut tracker = EconomyTracker::new();
+		let mut event = event("de_mirage", 3, "freezetime", 4000, 200);
+
+		if let Some(player) = event.player.as_mut() {
+			player.steam_id = "76561198000000002"
+				.parse()
+				.expect("valid SteamID");
+		}
+
+		tracker.update(&event);
+		assert!(tracker.current_round().is_none());
+	}
+}
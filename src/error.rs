@@ -20,6 +20,20 @@ pub enum Error {
 	#[error("Failed to save config file to disk: {0}")]
 	SaveConfig(String),
 
+	/// Something went wrong parsing VDF data.
+	#[error("Failed to parse VDF: {0}")]
+	ParseVdf(String),
+
+	/// Something went wrong binding the blocking server to its address.
+	#[cfg(feature = "blocking")]
+	#[error("Failed to bind server: {0}")]
+	Bind(String),
+
+	/// Something went wrong forwarding events to a sink.
+	#[cfg(feature = "server")]
+	#[error("Sink failed: {0}")]
+	Sink(String),
+
 	/// Something tried to access information about the current player on an
 	/// [`Event`](crate::Event) but there was none.
 	#[cfg(feature = "gokz")]
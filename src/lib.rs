@@ -2,7 +2,9 @@
 //!
 //! Also comes with a `server` feature that runs [`axum`](<https://developer.valvesoftware.com/wiki/Counter-Strike:_Global_Offensive_Game_State_Integration>)
 //! in the background listening for requests from CS:GO, sending them through a channel you can
-//! listen on.
+//! listen on. The `websocket` feature additionally lets the server rebroadcast events to e.g.
+//! browser overlays. For synchronous applications, the `blocking` feature provides a minimal
+//! server without an async runtime.
 
 #![deny(clippy::correctness, clippy::perf)]
 #![warn(clippy::complexity, clippy::cognitive_complexity, clippy::style)]
@@ -11,17 +13,32 @@
 pub mod csgo;
 pub use csgo::Event;
 
-#[cfg(any(feature = "server", feature = "gokz"))]
+pub mod stats;
+
+pub mod economy;
+
+pub mod scoreboard;
+
+#[cfg(feature = "serde")]
 mod error;
 
-#[cfg(any(feature = "server", feature = "gokz"))]
+#[cfg(feature = "serde")]
 pub use error::{Error, Result};
 
+#[cfg(any(feature = "server", feature = "blocking"))]
+mod config;
+
+#[cfg(any(feature = "server", feature = "blocking"))]
+pub use config::Config;
+
 #[cfg(feature = "server")]
 pub mod server;
 
 #[cfg(feature = "server")]
-pub use server::{Config, Server};
+pub use server::Server;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
 
 #[cfg(feature = "gokz")]
 pub mod gokz;
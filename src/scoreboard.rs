@@ -0,0 +1,348 @@
+//! A scoreboard view of the current match, similar to the one shown in-game.
+
+use {
+	crate::csgo::{Event, GamePhase, Map, MatchFormat, Period, RoundPhase, Team},
+	std::fmt,
+};
+
+/// The scoreboard of the current match.
+///
+/// Teams are identified by the side they started the match on, so they keep their position
+/// across halftime and overtime side switches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scoreboard {
+	/// The name of the map.
+	pub map: String,
+
+	/// The current phase of the game.
+	pub phase: GamePhase,
+
+	/// The round that is currently being played, starting at 1.
+	pub round: u64,
+
+	/// The period the current round is played in.
+	pub period: Period,
+
+	/// The team that started the match as Counter-Terrorists, followed by the team that started
+	/// as Terrorists.
+	pub teams: [TeamScoreboard; 2],
+}
+
+/// A single team on the [`Scoreboard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TeamScoreboard {
+	/// The team's name, if it has one.
+	pub name: Option<String>,
+
+	/// The side the team started the match on.
+	pub starting_side: Team,
+
+	/// The side the team is currently playing on.
+	pub side: Team,
+
+	/// The amount of rounds the team has won.
+	pub score: usize,
+
+	/// The team's players, sorted like the in-game scoreboard (by score, then kills, then deaths).
+	pub players: Vec<ScoreboardEntry>,
+}
+
+/// A single player on the [`Scoreboard`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreboardEntry {
+	/// The player's `SteamID64`.
+	pub steam_id: String,
+
+	/// The player's name.
+	pub name: String,
+
+	/// Whether the player is currently alive. Dead players are marked with an `x` in the text
+	/// rendering.
+	pub alive: bool,
+
+	/// How many kills the player has made.
+	pub kills: isize,
+
+	/// How many kills the player has assisted with.
+	pub assists: usize,
+
+	/// How many times the player has died.
+	pub deaths: usize,
+
+	/// How many times the player has been MVP.
+	pub mvps: usize,
+
+	/// The player's score.
+	pub score: usize,
+}
+
+impl Scoreboard {
+	/// Builds the scoreboard from an event.
+	///
+	/// Players are taken from [`Event::all_players`], which CS:GO only sends while spectating.
+	/// Without it the player lists are empty. Returns [`None`] if the event has no map.
+	///
+	/// Sides only switch once [`GamePhase::Halftime`] is over and the first round of the next
+	/// half starts. Until then, the round that just ended is still the current one.
+	pub fn from_event(event: &Event, format: &MatchFormat) -> Option<Self> {
+		let map = event.map.as_ref()?;
+		let round = current_round(event, map);
+		let switched = format.sides_switched(round);
+
+		let team = |starting_side: Team| {
+			let side = match (&starting_side, switched) {
+				(Team::CT, false) | (Team::T, true) => Team::CT,
+				_ => Team::T,
+			};
+
+			let stats = if side == Team::CT { &map.ct_stats } else { &map.t_stats };
+
+			let mut players = event
+				.all_players
+				.iter()
+				.filter(|(_, player)| player.team.as_ref() == Some(&side))
+				.map(|(steam_id, player)| {
+					let match_stats = player.match_stats.unwrap_or_default();
+
+					ScoreboardEntry {
+						steam_id: steam_id.clone(),
+						name: player.name.clone(),
+						alive: player.state.is_some_and(|state| state.health > 0),
+						kills: match_stats.kills,
+						assists: match_stats.assists,
+						deaths: match_stats.deaths,
+						mvps: match_stats.mvps,
+						score: match_stats.score,
+					}
+				})
+				.collect::<Vec<_>>();
+
+			players.sort_by(|a, b| {
+				b.score
+					.cmp(&a.score)
+					.then_with(|| b.kills.cmp(&a.kills))
+					.then_with(|| a.deaths.cmp(&b.deaths))
+					.then_with(|| a.name.cmp(&b.name))
+			});
+
+			TeamScoreboard {
+				name: stats.name.clone(),
+				starting_side,
+				side,
+				score: stats.score,
+				players,
+			}
+		};
+
+		Some(Self {
+			map: map.name.clone(),
+			phase: map.phase.clone(),
+			round,
+			period: format.period(round),
+			teams: [team(Team::CT), team(Team::T)],
+		})
+	}
+
+	/// The team currently playing on the given `side`.
+	pub fn team_on(&self, side: &Team) -> Option<&TeamScoreboard> {
+		self.teams.iter().find(|team| &team.side == side)
+	}
+}
+
+/// The round being played during `event`, starting at 1.
+///
+/// [`Map::round`] counts completed rounds, so it already includes the current round once that is
+/// over, during [`GamePhase::Halftime`] and after the game ended.
+fn current_round(event: &Event, map: &Map) -> u64 {
+	let round_over = matches!(map.phase, GamePhase::Halftime | GamePhase::GameOver)
+		|| event
+			.round
+			.as_ref()
+			.is_some_and(|round| round.phase == RoundPhase::Over);
+
+	let completed = u64::from(map.round);
+
+	if round_over {
+		completed.max(1)
+	} else {
+		completed + 1
+	}
+}
+
+impl fmt::Display for Scoreboard {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "{} | Round {} | {}", self.map, self.round, self.phase)?;
+
+		for team in &self.teams {
+			writeln!(f)?;
+			write!(f, "{team}")?;
+		}
+
+		Ok(())
+	}
+}
+
+impl fmt::Display for TeamScoreboard {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = self
+			.name
+			.as_deref()
+			.unwrap_or(match self.starting_side {
+				Team::T => "Team B",
+				_ => "Team A",
+			});
+
+		writeln!(f, "{name} ({}) - {}", self.side, self.score)?;
+		writeln!(
+			f,
+			"  {:<24} {:>4} {:>4} {:>4} {:>4} {:>6}",
+			"Player", "K", "A", "D", "MVP", "Score"
+		)?;
+
+		for player in &self.players {
+			let name = player.name.chars().take(24).collect::<String>();
+			let marker = if player.alive { ' ' } else { 'x' };
+
+			writeln!(
+				f,
+				"{marker} {name:<24} {:>4} {:>4} {:>4} {:>4} {:>6}",
+				player.kills, player.assists, player.deaths, player.mvps, player.score
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use {
+		super::*,
+		crate::csgo::test_util,
+		serde_json::{json, Value},
+	};
+
+	fn player(name: &str, team: &str, health: u8, stats: [usize; 4]) -> Value {
+		let [kills, deaths, mvps, score] = stats;
+
+		json!({
+			"name": name,
+			"team": team,
+			"state": test_util::player_state(health),
+			"match_stats": {
+				"kills": kills,
+				"assists": 0,
+				"deaths": deaths,
+				"mvps": mvps,
+				"score": score,
+			},
+		})
+	}
+
+	/// An event during a match between "Alpha", who started as CT and won 9 rounds, and an
+	/// unnamed team that won 6.
+	fn event(completed: u8, map_phase: &str, round_phase: &str, switched: bool) -> Event {
+		let (alpha, bravo) = if switched { ("T", "CT") } else { ("CT", "T") };
+
+		let mut alpha_stats = test_util::team_stats(9);
+		alpha_stats["name"] = json!("Alpha");
+
+		let mut map = test_util::map("de_mirage", completed);
+		map["phase"] = json!(map_phase);
+		map[format!("team_{}", alpha.to_lowercase())] = alpha_stats;
+		map[format!("team_{}", bravo.to_lowercase())] = test_util::team_stats(6);
+
+		test_util::from_json(json!({
+			"map": map,
+			"round": { "phase": round_phase },
+			"allplayers": {
+				"1": player("a1", alpha, 100, [10, 5, 1, 30]),
+				"2": player("a2", alpha, 100, [12, 8, 2, 30]),
+				"3": player("a3", alpha, 0, [12, 6, 0, 30]),
+				"4": player("a4", alpha, 100, [4, 9, 0, 10]),
+				"5": player("b1", bravo, 0, [7, 7, 1, 20]),
+			},
+		}))
+	}
+
+	fn assert_sides(scoreboard: &Scoreboard, round: u64, alpha_side: Team) {
+		let [alpha, bravo] = &scoreboard.teams;
+
+		assert_eq!(scoreboard.round, round);
+		assert_eq!(alpha.name.as_deref(), Some("Alpha"));
+		assert_eq!(alpha.starting_side, Team::CT);
+		assert_eq!(alpha.side, alpha_side);
+		assert_eq!(alpha.score, 9);
+		assert_eq!(alpha.players.len(), 4);
+		assert_eq!(bravo.name, None);
+		assert_eq!(bravo.score, 6);
+		assert_eq!(bravo.players.len(), 1);
+		assert_eq!(scoreboard.team_on(&alpha_side), Some(alpha));
+	}
+
+	#[test]
+	fn sides_switch_after_halftime() {
+		let format = MatchFormat::COMPETITIVE;
+		let scoreboard = |event| Scoreboard::from_event(&event, &format).expect("event has a map");
+
+		// Round 15 is being played.
+		assert_sides(&scoreboard(event(14, "live", "live", false)), 15, Team::CT);
+
+		// Round 15 is over.
+		assert_sides(&scoreboard(event(15, "live", "over", false)), 15, Team::CT);
+
+		// Halftime.
+		let intermission = scoreboard(event(15, "intermission", "over", false));
+		assert_sides(&intermission, 15, Team::CT);
+		assert_eq!(intermission.phase, GamePhase::Halftime);
+		assert_eq!(intermission.period, Period::Regulation { half: 1 });
+
+		// Round 16 starts with switched sides.
+		let second_half = scoreboard(event(15, "live", "freezetime", true));
+		assert_sides(&second_half, 16, Team::T);
+		assert_eq!(second_half.period, Period::Regulation { half: 2 });
+	}
+
+	#[test]
+	fn players_are_sorted_like_in_game() {
+		let scoreboard =
+			Scoreboard::from_event(&event(3, "live", "live", false), &MatchFormat::COMPETITIVE)
+				.expect("event has a map");
+
+		let names = scoreboard.teams[0]
+			.players
+			.iter()
+			.map(|player| player.name.as_str())
+			.collect::<Vec<_>>();
+
+		// By score, then kills, then fewest deaths.
+		assert_eq!(names, ["a3", "a2", "a1", "a4"]);
+	}
+
+	#[test]
+	fn display() {
+		let scoreboard =
+			Scoreboard::from_event(&event(3, "live", "live", false), &MatchFormat::COMPETITIVE)
+				.expect("event has a map");
+
+		let expected = "\
+de_mirage | Round 4 | live
+
+Alpha (CT) - 9
+  Player                      K    A    D  MVP  Score
+x a3                         12    0    6    0     30
+  a2                         12    0    8    2     30
+  a1                         10    0    5    1     30
+  a4                          4    0    9    0     10
+
+Team B (T) - 6
+  Player                      K    A    D  MVP  Score
+x b1                          7    0    7    1     20
+";
+
+		assert_eq!(scoreboard.to_string(), expected);
+	}
+}
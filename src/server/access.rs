@@ -0,0 +1,165 @@
+//! Access control for the server's routes.
+
+use {
+	super::Server,
+	axum::{
+		extract::{ConnectInfo, Query, State},
+		http::{header, HeaderValue, Method, Request, StatusCode},
+		middleware::Next,
+		response::{IntoResponse, Response},
+	},
+	serde::Deserialize,
+	std::{net::SocketAddr, sync::Arc},
+	tower_http::cors::{AllowOrigin, CorsLayer},
+	tracing::{debug, warn},
+};
+
+/// Who can reach a group of routes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Access {
+	/// Anyone who can reach the address the server listens on.
+	#[default]
+	Public,
+
+	/// Only requests coming from the machine the server runs on.
+	///
+	/// Other requests are rejected with `403 Forbidden`. If the address requests were sent from
+	/// isn't known, they are rejected as well, see [`Server::router`].
+	LocalOnly,
+}
+
+impl Access {
+	/// Checks whether a request sent from `remote_addr` is allowed.
+	fn allows(&self, remote_addr: Option<&ConnectInfo<SocketAddr>>) -> bool {
+		match (self, remote_addr) {
+			(Self::Public, _) => true,
+			(Self::LocalOnly, None) => {
+				warn!("rejecting request from unknown address, see `Server::router`");
+				false
+			}
+			(Self::LocalOnly, Some(ConnectInfo(addr))) => addr.ip().to_canonical().is_loopback(),
+		}
+	}
+}
+
+/// Query parameters of the read routes relevant to access control.
+#[derive(Debug, Deserialize)]
+struct TokenParams {
+	token: Option<String>,
+}
+
+/// Middleware for the ingest route.
+pub(crate) async fn ingest<B>(
+	State(server): State<Arc<Server>>,
+	request: Request<B>,
+	next: Next<B>,
+) -> Response {
+	if !server
+		.ingest_access
+		.allows(request.extensions().get())
+	{
+		debug!("rejecting ingest from remote address");
+		server.metrics.rejected_access();
+		return StatusCode::FORBIDDEN.into_response();
+	}
+
+	next.run(request).await
+}
+
+/// Middleware for the read routes.
+///
+/// If a reader token is configured, it has to be sent either as a bearer token in the
+/// `Authorization` header or, since browsers can't set headers for WebSockets and Server-Sent
+/// Events, as the `token` query parameter.
+pub(crate) async fn read<B>(
+	State(server): State<Arc<Server>>,
+	request: Request<B>,
+	next: Next<B>,
+) -> Response {
+	if !server
+		.read_access
+		.allows(request.extensions().get())
+	{
+		debug!("rejecting read from remote address");
+		server.metrics.rejected_access();
+		return StatusCode::FORBIDDEN.into_response();
+	}
+
+	if let Some(expected) = server.reader_token.as_deref() {
+		let bearer = request
+			.headers()
+			.get(header::AUTHORIZATION)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.strip_prefix("Bearer "))
+			.map(String::from);
+
+		let token = bearer.or_else(|| {
+			Query::<TokenParams>::try_from_uri(request.uri())
+				.ok()
+				.and_then(|Query(params)| params.token)
+		});
+
+		if token.as_deref() != Some(expected) {
+			debug!("rejecting read without valid token");
+			return StatusCode::UNAUTHORIZED.into_response();
+		}
+	}
+
+	next.run(request).await
+}
+
+/// Builds the CORS layer for the read routes from the allowed `origins`.
+///
+/// `*` allows any origin. Origins that aren't valid header values are ignored.
+pub(crate) fn cors(origins: &[String]) -> CorsLayer {
+	let allow_origin = if origins.iter().any(|origin| origin == "*") {
+		AllowOrigin::any()
+	} else {
+		AllowOrigin::list(origins.iter().filter_map(|origin| {
+			HeaderValue::from_str(origin)
+				.map_err(|error| warn!(%origin, %error, "ignoring invalid CORS origin"))
+				.ok()
+		}))
+	};
+
+	CorsLayer::new()
+		.allow_origin(allow_origin)
+		.allow_methods([Method::GET])
+		.allow_headers([header::AUTHORIZATION])
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		std::net::{IpAddr, Ipv4Addr, Ipv6Addr},
+	};
+
+	fn from(ip: impl Into<IpAddr>) -> Option<ConnectInfo<SocketAddr>> {
+		Some(ConnectInfo(SocketAddr::from((ip, 1337))))
+	}
+
+	#[test]
+	fn public_allows_everyone() {
+		assert!(Access::Public.allows(from(Ipv4Addr::LOCALHOST).as_ref()));
+		assert!(Access::Public.allows(from(Ipv4Addr::new(192, 168, 0, 2)).as_ref()));
+		assert!(Access::Public.allows(None));
+	}
+
+	#[test]
+	fn local_only_fails_closed() {
+		assert!(Access::LocalOnly.allows(from(Ipv4Addr::LOCALHOST).as_ref()));
+		assert!(!Access::LocalOnly.allows(from(Ipv4Addr::new(192, 168, 0, 2)).as_ref()));
+		assert!(!Access::LocalOnly.allows(None));
+	}
+
+	#[test]
+	fn local_only_allows_ipv6_loopback() {
+		let mapped = Ipv4Addr::LOCALHOST.to_ipv6_mapped();
+		let mapped_remote = Ipv4Addr::new(192, 168, 0, 2).to_ipv6_mapped();
+
+		assert!(Access::LocalOnly.allows(from(Ipv6Addr::LOCALHOST).as_ref()));
+		assert!(Access::LocalOnly.allows(from(mapped).as_ref()));
+		assert!(!Access::LocalOnly.allows(from(mapped_remote).as_ref()));
+	}
+}
@@ -0,0 +1,338 @@
+use {
+	super::{Access, Clients, Config, Deduplicator, Handlers, Metrics, Server, Subscribers},
+	serde::Deserialize,
+	std::{
+		collections::HashMap,
+		net::{IpAddr, Ipv4Addr},
+		sync::Arc,
+		time::Duration,
+	},
+	tokio::sync::broadcast,
+};
+
+/// Who is allowed to send events to a [`Server`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthPolicy {
+	/// Accept every event, regardless of its auth values.
+	#[default]
+	Ignore,
+
+	/// Only accept events that contain every auth value of the server's [`Config::auth`].
+	///
+	/// Other events are rejected with `401 Unauthorized`.
+	RequireConfig,
+}
+
+impl AuthPolicy {
+	/// Checks whether `auth` is allowed under this policy.
+	pub(crate) fn allows(&self, config: &Config, auth: &HashMap<String, String>) -> bool {
+		match self {
+			Self::Ignore => true,
+			Self::RequireConfig => config
+				.auth
+				.iter()
+				.all(|(key, value)| auth.get(key) == Some(value)),
+		}
+	}
+
+	/// Checks whether the raw `payload` is allowed under this policy.
+	///
+	/// Only the `auth` section is parsed, so payloads can be rejected before they are parsed as a
+	/// whole. Payloads that aren't valid JSON are never allowed unless every event is accepted.
+	pub(crate) fn allows_payload(&self, config: &Config, payload: &[u8]) -> bool {
+		#[derive(Deserialize)]
+		struct Payload {
+			#[serde(default)]
+			auth: HashMap<String, String>,
+		}
+
+		match self {
+			Self::Ignore => true,
+			Self::RequireConfig => serde_json::from_slice::<Payload>(payload)
+				.is_ok_and(|payload| self.allows(config, &payload.auth)),
+		}
+	}
+}
+
+/// Builder for [`Server`].
+#[derive(Default, Debug, Clone)]
+pub struct ServerBuilder {
+	capacity: Option<usize>,
+	ip: Option<IpAddr>,
+	route: Option<String>,
+	auth: Option<AuthPolicy>,
+	dedup_window: Option<Duration>,
+	track_clients: Option<bool>,
+	ingest_access: Option<Access>,
+	read_access: Option<Access>,
+	reader_token: Option<String>,
+	cors_origins: Option<Vec<String>>,
+	healthz_route: Option<String>,
+	readyz_route: Option<String>,
+	metrics_route: Option<String>,
+	state_route: Option<String>,
+	sse_route: Option<String>,
+	#[cfg(feature = "websocket")]
+	websocket_route: Option<String>,
+}
+
+impl ServerBuilder {
+	/// Sets the message buffer of each channel. Defaults to [`Server::DEFAULT_CAPACITY`].
+	///
+	/// Channels need room for at least one message, so `0` is treated as `1`.
+	pub fn capacity(mut self, capacity: usize) -> Self {
+		self.capacity = Some(capacity);
+		self
+	}
+
+	/// Sets the address of the interface to listen on. Defaults to `127.0.0.1`.
+	///
+	/// Use [`Ipv4Addr::UNSPECIFIED`] to receive events from other machines, e.g. on a LAN.
+	pub fn listen_on(mut self, ip: impl Into<IpAddr>) -> Self {
+		self.ip = Some(ip.into());
+		self
+	}
+
+	/// Sets the path CS:GO sends events to. Defaults to `/`.
+	///
+	/// A missing leading `/` is added, so `gsi` and `/gsi` are the same route. The same goes for
+	/// the paths of all other routes.
+	///
+	/// Use [`Server::install`] to install a game config pointing at this path.
+	pub fn route(mut self, route: impl Into<String>) -> Self {
+		self.route = Some(route.into());
+		self
+	}
+
+	/// Sets who is allowed to send events. Defaults to [`AuthPolicy::Ignore`].
+	pub fn auth(mut self, auth: AuthPolicy) -> Self {
+		self.auth = Some(auth);
+		self
+	}
+
+	/// Sets how long received payloads are remembered to detect retried deliveries. Defaults to
+	/// [`Server::DEFAULT_DEDUP_WINDOW`].
+	///
+	/// CS:GO resends a payload if it didn't get a response within [`Config::timeout`]. Payloads
+	/// with the same provider timestamp and content that arrive within this window are only
+	/// broadcast once. [`Duration::ZERO`] disables deduplication.
+	pub fn dedup_window(mut self, window: impl Into<Duration>) -> Self {
+		self.dedup_window = Some(window.into());
+		self
+	}
+
+	/// Sets whether the server keeps track of each game client's latest state. Defaults to `true`.
+	///
+	/// Without it, [`Server::clients`] and [`Server::client_state`] return nothing,
+	/// [`Server::subscribe_client`] never receives events and [`Filter`](super::Filter)s can't
+	/// compare events to the previous one.
+	pub fn track_clients(mut self, track_clients: bool) -> Self {
+		self.track_clients = Some(track_clients);
+		self
+	}
+
+	/// Enables a `GET` route at the given path responding with the latest event as JSON, e.g.
+	/// `/state`.
+	///
+	/// Responds with `204 No Content` if no event has been received yet. The returned sections can
+	/// be limited with the `sections` query parameter, e.g. `/state?sections=map,round`. Auth values
+	/// are never sent.
+	pub fn state(mut self, route: impl Into<String>) -> Self {
+		self.state_route = Some(route.into());
+		self
+	}
+
+	/// Enables a `GET` route at the given path streaming events as Server-Sent Events, e.g.
+	/// `/events`.
+	///
+	/// The latest event is sent immediately, followed by every event in which the requested
+	/// sections changed. Like with [`ServerBuilder::state`], sections can be limited with the
+	/// `sections` query parameter.
+	pub fn sse(mut self, route: impl Into<String>) -> Self {
+		self.sse_route = Some(route.into());
+		self
+	}
+
+	/// Enables a `GET` route at the given path that responds with `200 OK` while the server is
+	/// running, e.g. `/healthz`.
+	///
+	/// Health routes don't expose any data, so they aren't subject to
+	/// [`ServerBuilder::read_access`] or [`ServerBuilder::reader_token`].
+	pub fn healthz(mut self, route: impl Into<String>) -> Self {
+		self.healthz_route = Some(route.into());
+		self
+	}
+
+	/// Enables a `GET` route at the given path that responds with `200 OK` if the server is
+	/// [ready](Server::is_ready) and `503 Service Unavailable` otherwise, e.g. `/readyz`.
+	///
+	/// See [`ServerBuilder::healthz`].
+	pub fn readyz(mut self, route: impl Into<String>) -> Self {
+		self.readyz_route = Some(route.into());
+		self
+	}
+
+	/// Enables a `GET` route at the given path exposing the server's metrics in the Prometheus
+	/// text format, e.g. `/metrics`.
+	///
+	/// This includes counters for received, rejected, unparsable and duplicate events, how many
+	/// events lagging subscribers and event handlers missed, and histograms of payload sizes and the time between
+	/// events. Like the other read routes, it is subject to [`ServerBuilder::read_access`] and
+	/// [`ServerBuilder::reader_token`].
+	pub fn metrics(mut self, route: impl Into<String>) -> Self {
+		self.metrics_route = Some(route.into());
+		self
+	}
+
+	/// Enables a WebSocket route at the given path that rebroadcasts events as JSON, e.g. to
+	/// browser overlays.
+	///
+	/// Upon connecting, the latest event is sent immediately. Connections can limit the top-level
+	/// sections they receive with the `sections` query parameter, e.g. `/ws?sections=map,round`,
+	/// and only receive events in which those sections changed. Auth values are never sent.
+	#[cfg(feature = "websocket")]
+	pub fn websocket(mut self, route: impl Into<String>) -> Self {
+		self.websocket_route = Some(route.into());
+		self
+	}
+
+	/// Sets who can reach the route CS:GO sends events to. Defaults to [`Access::Public`].
+	///
+	/// Use [`Access::LocalOnly`] to keep the ingest route local while exposing the read routes
+	/// (e.g. [`ServerBuilder::state`]) on the network.
+	pub fn ingest_access(mut self, access: Access) -> Self {
+		self.ingest_access = Some(access);
+		self
+	}
+
+	/// Sets who can reach the read routes. Defaults to [`Access::Public`].
+	pub fn read_access(mut self, access: Access) -> Self {
+		self.read_access = Some(access);
+		self
+	}
+
+	/// Requires readers to send the given token to access the read routes.
+	///
+	/// This is separate from the game's [`Config::auth`]. The token can either be sent as a bearer
+	/// token in the `Authorization` header or as the `token` query parameter, since browsers can't
+	/// set headers for WebSockets and Server-Sent Events. Other requests are rejected with
+	/// `401 Unauthorized`.
+	pub fn reader_token(mut self, token: impl Into<String>) -> Self {
+		self.reader_token = Some(token.into());
+		self
+	}
+
+	/// Allows browsers on the given origin (e.g. `http://localhost:3000`) to access the read
+	/// routes. `*` allows any origin.
+	///
+	/// CORS is disabled by default, so browsers only allow same-origin requests.
+	pub fn cors_origin(mut self, origin: impl Into<String>) -> Self {
+		self.cors_origins
+			.get_or_insert_with(Vec::new)
+			.push(origin.into());
+
+		self
+	}
+
+	/// Builds the server, consuming the builder.
+	pub fn build(self, config: Config) -> Server {
+		let capacity = self
+			.capacity
+			.unwrap_or(Server::DEFAULT_CAPACITY)
+			.max(1);
+
+		let (event_emitter, _) = broadcast::channel(capacity);
+		let metrics = Arc::new(Metrics::default());
+		let dedup_window = self
+			.dedup_window
+			.unwrap_or(Server::DEFAULT_DEDUP_WINDOW);
+
+		Server {
+			config,
+			ip: self.ip.unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+			route: self
+				.route
+				.map_or_else(|| String::from("/"), normalize),
+			auth: self.auth.unwrap_or_default(),
+			track_clients: self.track_clients.unwrap_or(true),
+			ingest_access: self.ingest_access.unwrap_or_default(),
+			read_access: self.read_access.unwrap_or_default(),
+			reader_token: self.reader_token,
+			cors_origins: self.cors_origins,
+			healthz_route: self.healthz_route.map(normalize),
+			readyz_route: self.readyz_route.map(normalize),
+			metrics_route: self.metrics_route.map(normalize),
+			state_route: self.state_route.map(normalize),
+			sse_route: self.sse_route.map(normalize),
+			#[cfg(feature = "websocket")]
+			websocket_route: self.websocket_route.map(normalize),
+			event_emitter,
+			deduplicator: Arc::new(Deduplicator::new(dedup_window)),
+			clients: Arc::new(Clients::new(capacity)),
+			latest: Arc::default(),
+			subscribers: Arc::new(Subscribers::new(Arc::clone(&metrics))),
+			handlers: Arc::new(Handlers::new(Arc::clone(&metrics))),
+			metrics,
+			capacity,
+		}
+	}
+}
+
+/// Adds a leading `/` to `route` if it is missing, since [`axum::Router::route`] panics without
+/// one.
+fn normalize(route: String) -> String {
+	if route.starts_with('/') {
+		route
+	} else {
+		format!("/{route}")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn config() -> Config {
+		Config::builder().build("test", 8080_u16)
+	}
+
+	#[test]
+	fn payload_auth_is_checked_before_parsing() {
+		let config = Config::builder()
+			.auth("token", "secret")
+			.build("test", 8080_u16);
+
+		let valid = br#"{ "auth": { "token": "secret" }, "provider": "not an object" }"#;
+		let wrong = br#"{ "auth": { "token": "guess" } }"#;
+		let malformed = b"{ \"auth\": ";
+
+		assert!(AuthPolicy::RequireConfig.allows_payload(&config, valid));
+		assert!(!AuthPolicy::RequireConfig.allows_payload(&config, wrong));
+		assert!(!AuthPolicy::RequireConfig.allows_payload(&config, malformed));
+		assert!(!AuthPolicy::RequireConfig.allows_payload(&config, b"{}"));
+		assert!(AuthPolicy::Ignore.allows_payload(&config, malformed));
+	}
+
+	#[test]
+	fn zero_capacity_is_clamped() {
+		let server = Server::builder().capacity(0).build(config());
+
+		assert_eq!(server.capacity, 1);
+	}
+
+	#[test]
+	fn routes_get_a_leading_slash() {
+		let server = Server::builder()
+			.route("gsi")
+			.state("state")
+			.healthz("/healthz")
+			.build(config());
+
+		assert_eq!(server.route, "/gsi");
+		assert_eq!(server.state_route.as_deref(), Some("/state"));
+		assert_eq!(server.healthz_route.as_deref(), Some("/healthz"));
+		assert_eq!(server.uri(), "http://127.0.0.1:8080/gsi");
+
+		let _router = server.router::<()>();
+	}
+}
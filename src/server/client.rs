@@ -0,0 +1,282 @@
+use {
+	super::SteamId,
+	crate::csgo::Event,
+	std::{
+		collections::HashMap,
+		net::{IpAddr, SocketAddr},
+		sync::RwLock,
+		time::{Duration, SystemTime},
+	},
+	tokio::sync::broadcast,
+};
+
+/// Identifies a game client sending events to a [`Server`](super::Server).
+///
+/// Clients are identified by the SteamID of the player running the game. Events without provider
+/// information are attributed to the address they were sent from instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientId {
+	/// The `SteamID` of the player running the game.
+	SteamId(SteamId),
+
+	/// The IP address the events were sent from.
+	Addr(IpAddr),
+}
+
+impl ClientId {
+	/// The client that sent `event`.
+	///
+	/// Returns [`None`] if the event has neither provider information nor a remote address.
+	pub fn of(event: &Event) -> Option<Self> {
+		// `SteamID` is `Copy` with the `gokz` feature, but `String` isn't.
+		#[allow(clippy::clone_on_copy)]
+		if let Some(provider) = event.provider.as_ref() {
+			return Some(Self::SteamId(provider.steam_id.clone()));
+		}
+
+		event
+			.delivery
+			.as_ref()?
+			.remote_addr
+			.map(|addr| Self::Addr(addr.ip()))
+	}
+}
+
+impl From<IpAddr> for ClientId {
+	fn from(addr: IpAddr) -> Self {
+		Self::Addr(addr)
+	}
+}
+
+#[cfg(not(feature = "gokz"))]
+impl From<String> for ClientId {
+	fn from(steam_id: String) -> Self {
+		Self::SteamId(steam_id)
+	}
+}
+
+#[cfg(not(feature = "gokz"))]
+impl From<&str> for ClientId {
+	fn from(steam_id: &str) -> Self {
+		Self::SteamId(steam_id.to_owned())
+	}
+}
+
+#[cfg(feature = "gokz")]
+impl From<gokz_rs::SteamID> for ClientId {
+	fn from(steam_id: gokz_rs::SteamID) -> Self {
+		Self::SteamId(steam_id)
+	}
+}
+
+/// Information about a game client that has sent events to a [`Server`](super::Server).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+	/// The client's identity.
+	pub id: ClientId,
+
+	/// The address of the client's last delivery.
+	pub remote_addr: Option<SocketAddr>,
+
+	/// The auth values of the client's last event.
+	pub auth: HashMap<String, String>,
+
+	/// When the client's first event was received.
+	pub first_seen: SystemTime,
+
+	/// When the client's last event was received.
+	pub last_seen: SystemTime,
+
+	/// How many events the client has sent.
+	pub events: u64,
+}
+
+/// The state the server keeps for a single client.
+#[derive(Debug)]
+struct Entry {
+	/// Is [`None`] if somebody subscribed before the client sent anything.
+	info: Option<ClientInfo>,
+
+	/// The client's last event.
+	latest: Option<Event>,
+
+	/// Sender for the client's events.
+	event_emitter: broadcast::Sender<Event>,
+
+	/// Whether the client has sent events recently, see [`Clients::expire`].
+	connected: bool,
+}
+
+/// All clients known to a [`Server`](super::Server).
+#[derive(Debug)]
+pub(crate) struct Clients {
+	/// The message buffer for each client's channel.
+	capacity: usize,
+
+	entries: RwLock<HashMap<ClientId, Entry>>,
+}
+
+impl Clients {
+	pub(crate) fn new(capacity: usize) -> Self {
+		Self { capacity, entries: RwLock::default() }
+	}
+
+	/// Records `event` for the client that sent it and forwards it to the client's subscribers.
+	///
+	/// Returns the client's info if this is its first event since it connected.
+	pub(crate) fn update(&self, event: &Event) -> Option<ClientInfo> {
+		let id = ClientId::of(event)?;
+
+		let received_at = event
+			.received_at()
+			.unwrap_or_else(SystemTime::now);
+
+		let remote_addr = event
+			.delivery
+			.as_ref()
+			.and_then(|delivery| delivery.remote_addr);
+
+		let mut entries = self
+			.entries
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		let entry = entries
+			.entry(id.clone())
+			.or_insert_with(|| Self::entry(self.capacity));
+
+		let info = entry.info.get_or_insert_with(|| ClientInfo {
+			id,
+			remote_addr,
+			auth: HashMap::new(),
+			first_seen: received_at,
+			last_seen: received_at,
+			events: 0,
+		});
+
+		info.remote_addr = remote_addr.or(info.remote_addr);
+		info.auth.clone_from(&event.auth);
+		info.last_seen = received_at;
+		info.events += 1;
+
+		entry.latest = Some(event.clone());
+		_ = entry.event_emitter.send(event.clone());
+
+		let connected = !entry.connected;
+		entry.connected = true;
+
+		connected.then(|| info.clone())
+	}
+
+	/// Marks clients that haven't sent anything within `timeout` as disconnected.
+	///
+	/// Returns the clients that were connected until now.
+	pub(crate) fn expire(&self, timeout: Duration) -> Vec<ClientInfo> {
+		let now = SystemTime::now();
+
+		let mut entries = self
+			.entries
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		Self::prune(&mut entries);
+
+		entries
+			.values_mut()
+			.filter(|entry| entry.connected)
+			.filter_map(|entry| {
+				let info = entry.info.as_ref()?;
+				let silent = now
+					.duration_since(info.last_seen)
+					.unwrap_or_default();
+
+				if silent <= timeout {
+					return None;
+				}
+
+				entry.connected = false;
+				Some(info.clone())
+			})
+			.collect()
+	}
+
+	/// All clients that have sent at least one event.
+	pub(crate) fn list(&self) -> Vec<ClientInfo> {
+		self.entries
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.values()
+			.filter_map(|entry| entry.info.clone())
+			.collect()
+	}
+
+	/// The last event sent by the client with the given `id`.
+	pub(crate) fn latest(&self, id: &ClientId) -> Option<Event> {
+		self.entries
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.get(id)
+			.and_then(|entry| entry.latest.clone())
+	}
+
+	/// Subscribes to the events of the client with the given `id`.
+	///
+	/// The client doesn't need to have sent anything yet. Entries for such clients are removed
+	/// again once all of their subscribers are gone.
+	pub(crate) fn subscribe(&self, id: ClientId) -> broadcast::Receiver<Event> {
+		let mut entries = self
+			.entries
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		Self::prune(&mut entries);
+
+		entries
+			.entry(id)
+			.or_insert_with(|| Self::entry(self.capacity))
+			.event_emitter
+			.subscribe()
+	}
+
+	/// Removes entries of clients that never sent anything and have no subscribers left.
+	fn prune(entries: &mut HashMap<ClientId, Entry>) {
+		entries.retain(|_, entry| entry.info.is_some() || entry.event_emitter.receiver_count() > 0);
+	}
+
+	fn entry(capacity: usize) -> Entry {
+		let (event_emitter, _) = broadcast::channel(capacity);
+		Entry { info: None, latest: None, event_emitter, connected: false }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		std::net::{Ipv4Addr, Ipv6Addr},
+	};
+
+	fn len(clients: &Clients) -> usize {
+		clients
+			.entries
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.len()
+	}
+
+	#[test]
+	fn unknown_clients_are_forgotten_without_subscribers() {
+		let clients = Clients::new(1);
+
+		let receiver = clients.subscribe(ClientId::from(IpAddr::from(Ipv4Addr::LOCALHOST)));
+		assert_eq!(len(&clients), 1);
+
+		drop(receiver);
+		let _receiver = clients.subscribe(ClientId::from(IpAddr::from(Ipv6Addr::LOCALHOST)));
+		assert_eq!(len(&clients), 1);
+
+		clients.expire(Duration::ZERO);
+		assert_eq!(len(&clients), 1);
+		assert!(clients.list().is_empty());
+	}
+}
@@ -0,0 +1,160 @@
+use {
+	crate::csgo::Event,
+	std::{
+		collections::VecDeque,
+		sync::{Mutex, MutexGuard},
+		time::{Duration, Instant},
+	},
+};
+
+/// Detects payloads CS:GO sent more than once.
+///
+/// If the game doesn't receive a response within [`Config::timeout`](super::Config::timeout), it
+/// resends the exact same payload. Deliveries are considered duplicates if an event with the same
+/// provider timestamp and payload digest was seen within the configured window.
+#[derive(Debug)]
+pub(crate) struct Deduplicator {
+	state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+	/// How long deliveries are remembered.
+	window: Duration,
+
+	/// Recently seen deliveries, oldest first.
+	seen: VecDeque<Seen>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Seen {
+	at: Instant,
+	timestamp: Option<u64>,
+	digest: u64,
+}
+
+impl Deduplicator {
+	pub(crate) fn new(window: Duration) -> Self {
+		Self { state: Mutex::new(State { window, seen: VecDeque::new() }) }
+	}
+
+	/// Changes how long deliveries are remembered.
+	///
+	/// Since the deduplicator is shared, this affects every handle of the server it belongs to.
+	pub(crate) fn set_window(&self, window: Duration) {
+		self.lock().window = window;
+	}
+
+	/// Checks whether `event` has been seen before and remembers it if it hasn't.
+	///
+	/// Events without [delivery information](Event::delivery) are never considered duplicates.
+	pub(crate) fn is_duplicate(&self, event: &Event) -> bool {
+		let Some(delivery) = event.delivery.as_ref() else {
+			return false;
+		};
+
+		let mut state = self.lock();
+		let State { window, seen } = &mut *state;
+
+		if window.is_zero() {
+			return false;
+		}
+
+		let now = Instant::now();
+		let timestamp = event
+			.provider
+			.as_ref()
+			.map(|provider| provider.timestamp);
+
+		while seen
+			.front()
+			.is_some_and(|oldest| now.duration_since(oldest.at) > *window)
+		{
+			seen.pop_front();
+		}
+
+		if seen
+			.iter()
+			.any(|seen| seen.timestamp == timestamp && seen.digest == delivery.digest)
+		{
+			return true;
+		}
+
+		seen.push_back(Seen { at: now, timestamp, digest: delivery.digest });
+
+		false
+	}
+
+	fn lock(&self) -> MutexGuard<'_, State> {
+		self.state
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::csgo::{test_util, Delivery},
+		std::thread,
+	};
+
+	fn event(timestamp: u64, payload: &str) -> Event {
+		let mut event = test_util::event(timestamp);
+		event.delivery = Some(Delivery::now(payload.as_bytes()));
+		event
+	}
+
+	#[test]
+	fn retried_delivery_is_duplicate() {
+		let deduplicator = Deduplicator::new(Duration::from_secs(10));
+
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+		assert!(deduplicator.is_duplicate(&event(1, "a")));
+	}
+
+	#[test]
+	fn different_payload_or_timestamp_is_not_duplicate() {
+		let deduplicator = Deduplicator::new(Duration::from_secs(10));
+
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+		assert!(!deduplicator.is_duplicate(&event(1, "b")));
+		assert!(!deduplicator.is_duplicate(&event(2, "a")));
+	}
+
+	#[test]
+	fn deliveries_are_forgotten_after_window() {
+		let deduplicator = Deduplicator::new(Duration::from_millis(20));
+
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+		thread::sleep(Duration::from_millis(50));
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+	}
+
+	#[test]
+	fn zero_window_disables_deduplication() {
+		let deduplicator = Deduplicator::new(Duration::ZERO);
+
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+	}
+
+	#[test]
+	fn window_can_be_changed() {
+		let deduplicator = Deduplicator::new(Duration::from_secs(10));
+
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+		deduplicator.set_window(Duration::ZERO);
+		assert!(!deduplicator.is_duplicate(&event(1, "a")));
+	}
+
+	#[test]
+	fn events_without_delivery_are_not_duplicates() {
+		let deduplicator = Deduplicator::new(Duration::from_secs(10));
+		let event = Event { delivery: None, ..event(1, "a") };
+
+		assert!(!deduplicator.is_duplicate(&event));
+		assert!(!deduplicator.is_duplicate(&event));
+	}
+}
@@ -0,0 +1,190 @@
+use {
+	super::SteamId,
+	crate::csgo::{Event, PlayerActivity},
+	std::{fmt, ops, sync::Arc},
+};
+
+/// Decides which events a subscriber of [`Server::subscribe_filtered`](super::Server::subscribe_filtered) receives.
+///
+/// Some filters compare an event to the previous event sent by the same game client.
+///
+/// ```
+/// use schnose_gsi::server::Filter;
+///
+/// // Only events in which the map changed and the player is actually playing.
+/// let filter = Filter::MapChanged & Filter::Playing;
+///
+/// // Everything but events from the main menu.
+/// let filter = Filter::custom(|event| event.map.is_some());
+/// ```
+#[derive(Clone, Default)]
+pub enum Filter {
+	/// Every event.
+	#[default]
+	All,
+
+	/// Events in which the name of [`Event::map`] differs from the previous event, e.g. because
+	/// the player joined a server or the server changed maps.
+	MapChanged,
+
+	/// Events in which anything about [`Event::map`] differs from the previous event, e.g. the
+	/// round number or the score.
+	MapStateChanged,
+
+	/// Events in which the player's activity is [`PlayerActivity::Playing`].
+	Playing,
+
+	/// Events for the player with the given `SteamID`.
+	///
+	/// This is the player shown in [`Event::player`], so it also matches spectators watching them.
+	SteamId(SteamId),
+
+	/// Events for which the given function returns `true`.
+	Custom(Arc<dyn Fn(&Event) -> bool + Send + Sync>),
+
+	/// Events matching all of the given filters.
+	And(Vec<Filter>),
+
+	/// Events matching any of the given filters.
+	Or(Vec<Filter>),
+
+	/// Events not matching the given filter.
+	Not(Box<Filter>),
+}
+
+impl Filter {
+	/// Constructs a [`Filter::Custom`] from a function.
+	pub fn custom<F>(filter: F) -> Self
+	where
+		F: Fn(&Event) -> bool + Send + Sync + 'static, {
+		Self::Custom(Arc::new(filter))
+	}
+
+	/// Checks whether `event` passes this filter.
+	///
+	/// `previous` is the last event sent by the same game client, if any.
+	pub fn matches(&self, event: &Event, previous: Option<&Event>) -> bool {
+		match self {
+			Self::All => true,
+			Self::MapChanged => {
+				previous.is_none_or(|previous| map_name(previous) != map_name(event))
+			}
+			Self::MapStateChanged => previous.is_none_or(|previous| previous.map != event.map),
+			Self::Playing => event
+				.player
+				.as_ref()
+				.is_some_and(|player| player.activity == PlayerActivity::Playing),
+			Self::SteamId(steam_id) => event
+				.player
+				.as_ref()
+				.is_some_and(|player| &player.steam_id == steam_id),
+			Self::Custom(filter) => filter(event),
+			Self::And(filters) => filters
+				.iter()
+				.all(|filter| filter.matches(event, previous)),
+			Self::Or(filters) => filters
+				.iter()
+				.any(|filter| filter.matches(event, previous)),
+			Self::Not(filter) => !filter.matches(event, previous),
+		}
+	}
+}
+
+fn map_name(event: &Event) -> Option<&str> {
+	event.map.as_ref().map(|map| map.name.as_str())
+}
+
+impl fmt::Debug for Filter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::All => f.write_str("All"),
+			Self::MapChanged => f.write_str("MapChanged"),
+			Self::MapStateChanged => f.write_str("MapStateChanged"),
+			Self::Playing => f.write_str("Playing"),
+			Self::SteamId(steam_id) => f.debug_tuple("SteamId").field(steam_id).finish(),
+			Self::Custom(_) => f.write_str("Custom(..)"),
+			Self::And(filters) => f.debug_tuple("And").field(filters).finish(),
+			Self::Or(filters) => f.debug_tuple("Or").field(filters).finish(),
+			Self::Not(filter) => f.debug_tuple("Not").field(filter).finish(),
+		}
+	}
+}
+
+impl ops::BitAnd for Filter {
+	type Output = Self;
+
+	fn bitand(self, rhs: Self) -> Self::Output {
+		match self {
+			Self::And(mut filters) => {
+				filters.push(rhs);
+				Self::And(filters)
+			}
+			lhs => Self::And(vec![lhs, rhs]),
+		}
+	}
+}
+
+impl ops::BitOr for Filter {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		match self {
+			Self::Or(mut filters) => {
+				filters.push(rhs);
+				Self::Or(filters)
+			}
+			lhs => Self::Or(vec![lhs, rhs]),
+		}
+	}
+}
+
+impl ops::Not for Filter {
+	type Output = Self;
+
+	fn not(self) -> Self::Output {
+		match self {
+			Self::Not(filter) => *filter,
+			filter => Self::Not(Box::new(filter)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, crate::csgo::test_util, serde_json::json};
+
+	fn event(map: Option<(&str, u8)>) -> Event {
+		let map = map.map(|(name, round)| test_util::map(name, round));
+		test_util::from_json(json!({ "map": map }))
+	}
+
+	#[test]
+	fn map_changed_compares_names() {
+		let mirage = event(Some(("de_mirage", 1)));
+
+		assert!(Filter::MapChanged.matches(&mirage, None));
+		assert!(!Filter::MapChanged.matches(&event(Some(("de_mirage", 2))), Some(&mirage)));
+		assert!(Filter::MapChanged.matches(&event(Some(("de_inferno", 0))), Some(&mirage)));
+		assert!(Filter::MapChanged.matches(&event(None), Some(&mirage)));
+	}
+
+	#[test]
+	fn map_state_changed_compares_everything() {
+		let mirage = event(Some(("de_mirage", 1)));
+
+		assert!(Filter::MapStateChanged.matches(&event(Some(("de_mirage", 2))), Some(&mirage)));
+		assert!(!Filter::MapStateChanged.matches(&mirage.clone(), Some(&mirage)));
+	}
+
+	#[test]
+	fn combinators() {
+		let mirage = event(Some(("de_mirage", 1)));
+		let has_map = Filter::custom(|event| event.map.is_some());
+
+		assert!((Filter::All & has_map.clone()).matches(&mirage, None));
+		assert!(!(Filter::All & has_map.clone()).matches(&event(None), None));
+		assert!((!has_map.clone() | Filter::Playing).matches(&event(None), None));
+		assert!(!(!Filter::All).matches(&mirage, None));
+		assert!(matches!(!!has_map, Filter::Custom(_)));
+	}
+}
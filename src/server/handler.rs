@@ -0,0 +1,446 @@
+use {
+	super::{Backpressure, ClientInfo, Clients, Metrics},
+	crate::Event,
+	async_trait::async_trait,
+	std::{
+		net::SocketAddr,
+		sync::{
+			atomic::{AtomicBool, Ordering},
+			Arc, RwLock, Weak,
+		},
+		time::Duration,
+	},
+	tokio::sync::{broadcast, mpsc},
+	tracing::{error, warn},
+};
+
+/// How often the server checks for clients that stopped sending events.
+const DISCONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A consumer of server events, registered via [`Server::register`](super::Server::register).
+///
+/// Every method does nothing by default, so only the ones you're interested in need to be
+/// implemented. Implementations need the [`async_trait`](super::async_trait) attribute.
+///
+/// ```no_run
+/// use schnose_gsi::{
+///     server::{async_trait, EventHandler},
+///     Event, Server,
+/// };
+///
+/// struct MapLogger;
+///
+/// #[async_trait]
+/// impl EventHandler for MapLogger {
+///     async fn on_event(&self, event: &Event) {
+///         println!("{:?}", event.map);
+///     }
+/// }
+///
+/// # async fn run(server: Server) {
+/// server.register(MapLogger);
+/// # }
+/// ```
+#[async_trait]
+pub trait EventHandler: Send + Sync + 'static {
+	/// Called for every event passed on to subscribers.
+	async fn on_event(&self, _event: &Event) {
+	}
+
+	/// Called when a game client sends its first event, or its first event after it
+	/// [disconnected](EventHandler::on_disconnect).
+	async fn on_connect(&self, _client: &ClientInfo) {
+	}
+
+	/// Called when a game client hasn't sent an event for longer than
+	/// [`Config::heartbeat`](super::Config::heartbeat) plus [`Config::buffer`](super::Config::buffer).
+	async fn on_disconnect(&self, _client: &ClientInfo) {
+	}
+
+	/// Called when a payload sent to the server isn't a valid event.
+	///
+	/// Payloads rejected by the server's [`AuthPolicy`](super::AuthPolicy) never get here.
+	async fn on_parse_error(&self, _error: &ParseError) {
+	}
+}
+
+/// A payload that couldn't be parsed as an [`Event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	/// Why the payload couldn't be parsed.
+	pub message: String,
+
+	/// The payload that was received.
+	pub payload: Vec<u8>,
+
+	/// The address the payload was sent from, if known.
+	pub remote_addr: Option<SocketAddr>,
+}
+
+/// A single call to an [`EventHandler`].
+#[derive(Debug)]
+pub(crate) enum Callback {
+	Event(Box<Event>),
+	Connect(ClientInfo),
+	Disconnect(ClientInfo),
+	ParseError(ParseError),
+}
+
+impl Callback {
+	async fn call(&self, handler: &dyn EventHandler) {
+		match self {
+			Self::Event(event) => handler.on_event(event).await,
+			Self::Connect(client) => handler.on_connect(client).await,
+			Self::Disconnect(client) => handler.on_disconnect(client).await,
+			Self::ParseError(error) => handler.on_parse_error(error).await,
+		}
+	}
+}
+
+/// The sending half of a handler's queue.
+#[derive(Debug, Clone)]
+enum Queue {
+	/// Drops the oldest callbacks once full.
+	Broadcast(broadcast::Sender<Arc<Callback>>),
+
+	/// Waits for room once full.
+	Mpsc(mpsc::Sender<Arc<Callback>>),
+}
+
+/// The receiving half of a handler's queue.
+#[derive(Debug)]
+enum Callbacks {
+	Broadcast { receiver: broadcast::Receiver<Arc<Callback>>, coalesce: bool },
+	Mpsc(mpsc::Receiver<Arc<Callback>>),
+}
+
+impl Callbacks {
+	/// Waits for the next callbacks to make.
+	///
+	/// Callbacks that were dropped or coalesced are counted in `metrics`. Returns [`None`] once
+	/// the handlers have been dropped.
+	async fn next(&mut self, metrics: &Metrics) -> Option<Vec<Arc<Callback>>> {
+		let (receiver, coalesce) = match self {
+			Self::Mpsc(receiver) => {
+				return receiver
+					.recv()
+					.await
+					.map(|callback| vec![callback])
+			}
+			Self::Broadcast { receiver, coalesce } => (receiver, *coalesce),
+		};
+
+		let mut callbacks = loop {
+			match receiver.recv().await {
+				Ok(callback) => break vec![callback],
+				Err(broadcast::error::RecvError::Lagged(dropped)) => {
+					warn!(dropped, "event handler can't keep up");
+					metrics.handler_dropped(dropped);
+				}
+				Err(broadcast::error::RecvError::Closed) => return None,
+			}
+		};
+
+		if coalesce {
+			loop {
+				match receiver.try_recv() {
+					Ok(callback) => callbacks.push(callback),
+					Err(broadcast::error::TryRecvError::Lagged(dropped)) => {
+						metrics.handler_dropped(dropped);
+					}
+					Err(_) => break,
+				}
+			}
+
+			metrics.handler_dropped(coalesce_events(&mut callbacks));
+		}
+
+		Some(callbacks)
+	}
+}
+
+/// Removes all [`Callback::Event`]s from `callbacks` except for the latest one.
+///
+/// Returns how many were removed.
+fn coalesce_events(callbacks: &mut Vec<Arc<Callback>>) -> u64 {
+	let is_event = |callback: &Arc<Callback>| matches!(**callback, Callback::Event(_));
+
+	let Some(latest) = callbacks.iter().rposition(is_event) else {
+		return 0;
+	};
+
+	let before = callbacks.len();
+	let mut index = 0;
+
+	callbacks.retain(|callback| {
+		let keep = index >= latest || !is_event(callback);
+		index += 1;
+		keep
+	});
+
+	(before - callbacks.len()) as u64
+}
+
+/// All [`EventHandler`]s registered on a [`Server`](super::Server).
+///
+/// Every handler has its own queue and task, so a slow handler doesn't delay the others, but
+/// calls to the same handler are made one after another in the order they were dispatched.
+#[derive(Debug)]
+pub(crate) struct Handlers {
+	queues: RwLock<Vec<Queue>>,
+
+	/// Whether the task detecting disconnected clients has been started.
+	watching: AtomicBool,
+
+	metrics: Arc<Metrics>,
+}
+
+impl Handlers {
+	pub(crate) fn new(metrics: Arc<Metrics>) -> Self {
+		Self { queues: RwLock::default(), watching: AtomicBool::default(), metrics }
+	}
+
+	/// Spawns a task calling `handler` for every dispatched callback, queueing up to `capacity`
+	/// callbacks according to `policy`.
+	///
+	/// The task stops once these handlers are dropped.
+	pub(crate) fn register(
+		&self,
+		handler: Arc<dyn EventHandler>,
+		policy: Backpressure,
+		capacity: usize,
+	) {
+		let (queue, mut callbacks) = match policy {
+			Backpressure::Block => {
+				let (sender, receiver) = mpsc::channel(capacity);
+				(Queue::Mpsc(sender), Callbacks::Mpsc(receiver))
+			}
+			Backpressure::DropOldest | Backpressure::Coalesce => {
+				let (sender, receiver) = broadcast::channel(capacity);
+				let coalesce = policy == Backpressure::Coalesce;
+				(Queue::Broadcast(sender), Callbacks::Broadcast { receiver, coalesce })
+			}
+		};
+
+		let metrics = Arc::clone(&self.metrics);
+
+		tokio::spawn(async move {
+			while let Some(batch) = callbacks.next(&metrics).await {
+				for callback in batch {
+					let handler = Arc::clone(&handler);
+
+					// Running every call in its own task keeps a panicking handler from taking
+					// down the queue.
+					let call = tokio::spawn(async move { callback.call(&*handler).await });
+
+					if let Err(error) = call.await {
+						error!(%error, "event handler panicked");
+					}
+				}
+			}
+		});
+
+		self.queues
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.push(queue);
+	}
+
+	/// Queues `callback` for every registered handler.
+	///
+	/// Only waits for handlers with a [`Backpressure::Block`] policy whose queue is full.
+	pub(crate) async fn dispatch(&self, callback: Callback) {
+		let queues = self
+			.queues
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.clone();
+
+		let callback = Arc::new(callback);
+
+		for queue in queues {
+			match queue {
+				Queue::Broadcast(sender) => {
+					_ = sender.send(Arc::clone(&callback));
+				}
+				Queue::Mpsc(sender) => {
+					_ = sender.send(Arc::clone(&callback)).await;
+				}
+			}
+		}
+	}
+
+	/// Whether no handlers have been registered.
+	pub(crate) fn is_empty(&self) -> bool {
+		self.queues
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.is_empty()
+	}
+
+	/// Spawns a task dispatching [`Callback::Disconnect`] for clients that haven't sent anything
+	/// within `timeout`, unless it is already running.
+	///
+	/// The task stops once `handlers` are dropped.
+	pub(crate) fn watch(handlers: &Arc<Self>, clients: Arc<Clients>, timeout: Duration) {
+		if handlers.watching.swap(true, Ordering::Relaxed) {
+			return;
+		}
+
+		let handlers = Arc::downgrade(handlers);
+
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(DISCONNECT_INTERVAL);
+
+			loop {
+				interval.tick().await;
+
+				let Some(handlers) = Weak::upgrade(&handlers) else {
+					return;
+				};
+
+				for client in clients.expire(timeout) {
+					handlers
+						.dispatch(Callback::Disconnect(client))
+						.await;
+				}
+			}
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::csgo::test_util,
+		std::future::Future,
+		tokio::{sync::Semaphore, time::timeout},
+	};
+
+	/// Records every call and then waits until the test lets it continue.
+	struct Recorder {
+		calls: mpsc::UnboundedSender<String>,
+		gate: Arc<Semaphore>,
+	}
+
+	impl Recorder {
+		async fn record(&self, call: String) {
+			_ = self.calls.send(call);
+			self.gate
+				.acquire()
+				.await
+				.expect("gate is never closed")
+				.forget();
+		}
+	}
+
+	#[async_trait]
+	impl EventHandler for Recorder {
+		async fn on_event(&self, event: &Event) {
+			let timestamp = event
+				.provider
+				.as_ref()
+				.map_or(0, |provider| provider.timestamp);
+
+			self.record(timestamp.to_string()).await;
+		}
+
+		async fn on_parse_error(&self, error: &ParseError) {
+			self.record(error.message.clone()).await;
+		}
+	}
+
+	fn event(timestamp: u64) -> Callback {
+		Callback::Event(Box::new(test_util::event(timestamp)))
+	}
+
+	fn parse_error(message: &str) -> Callback {
+		Callback::ParseError(ParseError {
+			message: message.to_owned(),
+			payload: Vec::new(),
+			remote_addr: None,
+		})
+	}
+
+	/// Registers a [`Recorder`] that is blocked in its first call.
+	async fn blocked(
+		policy: Backpressure,
+		capacity: usize,
+	) -> (Handlers, Arc<Metrics>, mpsc::UnboundedReceiver<String>, Arc<Semaphore>) {
+		let metrics = Arc::new(Metrics::default());
+		let handlers = Handlers::new(Arc::clone(&metrics));
+		let (calls, mut recorded) = mpsc::unbounded_channel();
+		let gate = Arc::new(Semaphore::new(0));
+
+		handlers.register(Arc::new(Recorder { calls, gate: Arc::clone(&gate) }), policy, capacity);
+		handlers.dispatch(event(0)).await;
+		assert_eq!(recorded.recv().await.as_deref(), Some("0"));
+
+		(handlers, metrics, recorded, gate)
+	}
+
+	async fn completes(future: impl Future<Output = ()>) -> bool {
+		timeout(Duration::from_millis(100), future)
+			.await
+			.is_ok()
+	}
+
+	#[test]
+	fn only_the_latest_event_is_kept() {
+		let mut callbacks = vec![event(1), parse_error("a"), event(2), event(3), parse_error("b")]
+			.into_iter()
+			.map(Arc::new)
+			.collect::<Vec<_>>();
+
+		assert_eq!(coalesce_events(&mut callbacks), 2);
+		assert!(matches!(*callbacks[0], Callback::ParseError(_)));
+		assert!(matches!(*callbacks[1], Callback::Event(_)));
+		assert!(matches!(*callbacks[2], Callback::ParseError(_)));
+	}
+
+	#[tokio::test]
+	async fn slow_handler_drops_oldest() {
+		let (handlers, metrics, mut recorded, gate) = blocked(Backpressure::DropOldest, 2).await;
+
+		for timestamp in 1..=5 {
+			assert!(completes(handlers.dispatch(event(timestamp))).await);
+		}
+
+		gate.add_permits(10);
+
+		assert_eq!(recorded.recv().await.as_deref(), Some("4"));
+		assert_eq!(recorded.recv().await.as_deref(), Some("5"));
+		assert_eq!(metrics.handler_dropped_total(), 3);
+	}
+
+	#[tokio::test]
+	async fn slow_handler_coalesces_events() {
+		let (handlers, metrics, mut recorded, gate) = blocked(Backpressure::Coalesce, 8).await;
+
+		for callback in [event(1), parse_error("a"), event(2), event(3)] {
+			assert!(completes(handlers.dispatch(callback)).await);
+		}
+
+		gate.add_permits(10);
+
+		assert_eq!(recorded.recv().await.as_deref(), Some("a"));
+		assert_eq!(recorded.recv().await.as_deref(), Some("3"));
+		assert_eq!(metrics.handler_dropped_total(), 2);
+	}
+
+	#[tokio::test]
+	async fn blocking_handler_waits() {
+		let (handlers, metrics, mut recorded, gate) = blocked(Backpressure::Block, 1).await;
+
+		assert!(completes(handlers.dispatch(event(1))).await);
+		assert!(!completes(handlers.dispatch(event(2))).await);
+
+		gate.add_permits(10);
+		handlers.dispatch(event(3)).await;
+
+		assert_eq!(recorded.recv().await.as_deref(), Some("1"));
+		assert_eq!(recorded.recv().await.as_deref(), Some("3"));
+		assert_eq!(metrics.handler_dropped_total(), 0);
+	}
+}
@@ -0,0 +1,92 @@
+//! Health and readiness routes for supervisors and container healthchecks.
+
+use {
+	super::Server,
+	axum::{extract::State, http::StatusCode},
+	std::sync::Arc,
+};
+
+/// `GET` handler that always succeeds while the server is running.
+pub(crate) async fn healthz() -> &'static str {
+	"ok"
+}
+
+/// `GET` handler that only succeeds if the server is [ready](Server::is_ready).
+///
+/// Responds with `503 Service Unavailable` otherwise.
+pub(crate) async fn readyz(State(server): State<Arc<Server>>) -> (StatusCode, &'static str) {
+	if server.is_ready() {
+		(StatusCode::OK, "ok")
+	} else {
+		(StatusCode::SERVICE_UNAVAILABLE, "no recent events")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::{
+			csgo::test_util as fixtures,
+			server::{test_util, Access, Config},
+		},
+		std::time::Duration,
+	};
+
+	fn server(heartbeat: Duration) -> Server {
+		Server::builder()
+			.healthz("/healthz")
+			.readyz("/readyz")
+			.state("/state")
+			.read_access(Access::LocalOnly)
+			.reader_token("secret")
+			.build(
+				Config::builder()
+					.heartbeat(heartbeat)
+					.buffer(Duration::ZERO)
+					.build("test", 8080_u16),
+			)
+	}
+
+	#[tokio::test]
+	async fn healthz() {
+		let server = server(Duration::from_secs(30));
+
+		// Neither the read access nor the reader token apply to health routes.
+		let response = test_util::send(&server, test_util::get("/healthz")).await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(test_util::first_chunk(response).await, "ok");
+
+		assert_eq!(
+			test_util::status(&server, test_util::get("/state")).await,
+			StatusCode::FORBIDDEN
+		);
+	}
+
+	#[tokio::test]
+	async fn readyz() {
+		let server = server(Duration::from_secs(30));
+
+		assert_eq!(
+			test_util::status(&server, test_util::get("/readyz")).await,
+			StatusCode::SERVICE_UNAVAILABLE
+		);
+
+		server.publish(fixtures::event(1)).await;
+
+		assert_eq!(test_util::status(&server, test_util::get("/readyz")).await, StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn readyz_after_silence() {
+		let server = server(Duration::from_millis(20));
+		server.publish(fixtures::event(1)).await;
+
+		tokio::time::sleep(Duration::from_millis(50)).await;
+
+		assert_eq!(
+			test_util::status(&server, test_util::get("/readyz")).await,
+			StatusCode::SERVICE_UNAVAILABLE
+		);
+	}
+}
@@ -0,0 +1,296 @@
+//! Internal counters of the server, exposed in the Prometheus text format.
+
+use {
+	super::Server,
+	axum::{extract::State, http::header, response::IntoResponse},
+	std::{
+		fmt::{self, Write},
+		sync::{
+			atomic::{AtomicU64, Ordering},
+			Arc, Mutex,
+		},
+		time::Instant,
+	},
+};
+
+/// Bucket bounds of the payload size histogram, in bytes.
+const PAYLOAD_SIZE_BUCKETS: &[f64] =
+	&[256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0];
+
+/// Bucket bounds of the inter-arrival time histogram, in seconds.
+const INTER_ARRIVAL_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Counters and histograms describing what the server received.
+#[derive(Debug)]
+pub(crate) struct Metrics {
+	/// Payloads received on the ingest route.
+	payloads: AtomicU64,
+
+	/// Events passed on to subscribers.
+	events: AtomicU64,
+
+	/// Payloads that weren't valid events.
+	parse_errors: AtomicU64,
+
+	/// Events rejected by the [`AuthPolicy`](super::AuthPolicy).
+	rejected_auth: AtomicU64,
+
+	/// Requests rejected by an [`Access`](super::Access) policy.
+	rejected_access: AtomicU64,
+
+	/// Retried deliveries that were dropped.
+	duplicates: AtomicU64,
+
+	/// Events missed by lagging subscribers, including the server's own (e.g. WebSocket
+	/// connections).
+	///
+	/// This only ever grows, unlike the sum of [`SubscriberStats::missed`](super::SubscriberStats)
+	/// of the subscribers that are still alive.
+	missed: AtomicU64,
+
+	/// Calls lagging event handlers missed.
+	handler_dropped: AtomicU64,
+
+	payload_size: Histogram,
+	inter_arrival: Histogram,
+
+	/// When the last event was passed on to subscribers.
+	last_event: Mutex<Option<Instant>>,
+}
+
+impl Default for Metrics {
+	fn default() -> Self {
+		Self {
+			payloads: AtomicU64::default(),
+			events: AtomicU64::default(),
+			parse_errors: AtomicU64::default(),
+			rejected_auth: AtomicU64::default(),
+			rejected_access: AtomicU64::default(),
+			duplicates: AtomicU64::default(),
+			missed: AtomicU64::default(),
+			handler_dropped: AtomicU64::default(),
+			payload_size: Histogram::new(PAYLOAD_SIZE_BUCKETS),
+			inter_arrival: Histogram::new(INTER_ARRIVAL_BUCKETS),
+			last_event: Mutex::default(),
+		}
+	}
+}
+
+impl Metrics {
+	pub(crate) fn payload(&self, size: usize) {
+		self.payloads.fetch_add(1, Ordering::Relaxed);
+		self.payload_size.observe(size as f64);
+	}
+
+	pub(crate) fn event(&self) {
+		self.events.fetch_add(1, Ordering::Relaxed);
+
+		let now = Instant::now();
+		let previous = self
+			.last_event
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.replace(now);
+
+		if let Some(previous) = previous {
+			self.inter_arrival
+				.observe(now.duration_since(previous).as_secs_f64());
+		}
+	}
+
+	/// When the last event was passed on to subscribers.
+	pub(crate) fn last_event(&self) -> Option<Instant> {
+		*self
+			.last_event
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	pub(crate) fn parse_error(&self) {
+		self.parse_errors.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn rejected_auth(&self) {
+		self.rejected_auth.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn rejected_access(&self) {
+		self.rejected_access
+			.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn duplicate(&self) {
+		self.duplicates.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn missed(&self, missed: u64) {
+		self.missed.fetch_add(missed, Ordering::Relaxed);
+	}
+
+	pub(crate) fn handler_dropped(&self, dropped: u64) {
+		self.handler_dropped
+			.fetch_add(dropped, Ordering::Relaxed);
+	}
+
+	#[cfg(test)]
+	pub(crate) fn handler_dropped_total(&self) -> u64 {
+		self.handler_dropped.load(Ordering::Relaxed)
+	}
+
+	/// Renders all metrics of `server` in the Prometheus text format.
+	fn render(&self, server: &Server) -> Result<String, fmt::Error> {
+		let mut out = String::new();
+
+		let counters = [
+			("gsi_payloads_total", "Payloads received on the ingest route.", &self.payloads),
+			("gsi_events_total", "Events passed on to subscribers.", &self.events),
+			("gsi_parse_errors_total", "Payloads that weren't valid events.", &self.parse_errors),
+			("gsi_duplicates_total", "Retried deliveries that were dropped.", &self.duplicates),
+			(
+				"gsi_handler_dropped_total",
+				"Calls dropped because an event handler couldn't keep up.",
+				&self.handler_dropped,
+			),
+		];
+
+		for (name, help, counter) in counters {
+			writeln!(out, "# HELP {name} {help}")?;
+			writeln!(out, "# TYPE {name} counter")?;
+			writeln!(out, "{name} {}", counter.load(Ordering::Relaxed))?;
+		}
+
+		writeln!(out, "# HELP gsi_rejected_total Requests that were rejected.")?;
+		writeln!(out, "# TYPE gsi_rejected_total counter")?;
+		for (reason, counter) in [("auth", &self.rejected_auth), ("access", &self.rejected_access)]
+		{
+			let count = counter.load(Ordering::Relaxed);
+			writeln!(out, "gsi_rejected_total{{reason=\"{reason}\"}} {count}")?;
+		}
+
+		let missed = self.missed.load(Ordering::Relaxed);
+
+		writeln!(out, "# HELP gsi_missed_events_total Events missed by lagging subscribers.")?;
+		writeln!(out, "# TYPE gsi_missed_events_total counter")?;
+		writeln!(out, "gsi_missed_events_total {missed}")?;
+
+		writeln!(out, "# HELP gsi_subscribers Filtered subscribers that are still alive.")?;
+		writeln!(out, "# TYPE gsi_subscribers gauge")?;
+		writeln!(out, "gsi_subscribers {}", server.subscribers().len())?;
+
+		writeln!(out, "# HELP gsi_clients Game clients that have sent events.")?;
+		writeln!(out, "# TYPE gsi_clients gauge")?;
+		writeln!(out, "gsi_clients {}", server.clients().len())?;
+
+		self.payload_size.render(
+			&mut out,
+			"gsi_payload_size_bytes",
+			"Size of payloads sent to the ingest route.",
+		)?;
+
+		self.inter_arrival.render(
+			&mut out,
+			"gsi_event_interarrival_seconds",
+			"Time between two events being passed on to subscribers.",
+		)?;
+
+		Ok(out)
+	}
+}
+
+/// A Prometheus histogram with fixed bucket bounds.
+#[derive(Debug)]
+struct Histogram {
+	bounds: &'static [f64],
+	inner: Mutex<HistogramInner>,
+}
+
+#[derive(Debug)]
+struct HistogramInner {
+	/// Non-cumulative counts per bucket, followed by the `+Inf` bucket.
+	buckets: Vec<u64>,
+	sum: f64,
+	count: u64,
+}
+
+impl Histogram {
+	fn new(bounds: &'static [f64]) -> Self {
+		let inner = HistogramInner { buckets: vec![0; bounds.len() + 1], sum: 0.0, count: 0 };
+		Self { bounds, inner: Mutex::new(inner) }
+	}
+
+	fn observe(&self, value: f64) {
+		let bucket = self
+			.bounds
+			.iter()
+			.position(|&bound| value <= bound)
+			.unwrap_or(self.bounds.len());
+
+		let mut inner = self
+			.inner
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		inner.buckets[bucket] += 1;
+		inner.sum += value;
+		inner.count += 1;
+	}
+
+	fn render(&self, out: &mut String, name: &str, help: &str) -> fmt::Result {
+		let inner = self
+			.inner
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		writeln!(out, "# HELP {name} {help}")?;
+		writeln!(out, "# TYPE {name} histogram")?;
+
+		let mut cumulative = 0;
+		for (bound, count) in self.bounds.iter().zip(&inner.buckets) {
+			cumulative += count;
+			writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {cumulative}")?;
+		}
+
+		writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", inner.count)?;
+		writeln!(out, "{name}_sum {}", inner.sum)?;
+		writeln!(out, "{name}_count {}", inner.count)
+	}
+}
+
+/// `GET` handler responding with all metrics in the Prometheus text format.
+pub(crate) async fn handler(State(server): State<Arc<Server>>) -> impl IntoResponse {
+	let metrics = server.metrics.render(&server).unwrap_or_default();
+
+	([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics)
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::{
+			csgo::Event,
+			server::{Backpressure, Filter, Subscribers},
+		},
+	};
+
+	#[tokio::test]
+	async fn missed_events_are_counted_after_subscriber_is_gone() {
+		let event = serde_json::from_value::<Event>(serde_json::json!({})).expect("valid event");
+		let metrics = Arc::new(Metrics::default());
+		let subscribers = Subscribers::new(Arc::clone(&metrics));
+		let mut receiver = subscribers.subscribe_with(Filter::All, Backpressure::Coalesce, 1);
+
+		for _ in 0..3 {
+			subscribers.dispatch(&event, None).await;
+		}
+
+		assert!(receiver.recv().await.is_some());
+		assert_eq!(receiver.missed(), 2);
+
+		drop(receiver);
+		subscribers.dispatch(&event, None).await;
+
+		assert!(subscribers.stats().is_empty());
+		assert_eq!(metrics.missed.load(Ordering::Relaxed), 2);
+	}
+}
@@ -1,37 +1,222 @@
 //! Module containing the GSI server implementation for listening for CS:GO events.
 
 use {
-	crate::Result,
-	axum::{extract::State, http::StatusCode, routing::post, Json, Router},
-	std::{net::SocketAddr, sync::Arc},
+	crate::{csgo::Delivery, Result},
+	axum::{
+		body::Bytes,
+		extract::{ConnectInfo, State},
+		http::StatusCode,
+		middleware::from_fn_with_state,
+		routing::{get, post},
+		Router,
+	},
+	std::{
+		net::{IpAddr, Ipv4Addr, SocketAddr},
+		path::PathBuf,
+		sync::{Arc, RwLock},
+		time::Duration,
+	},
 	tokio::sync::{broadcast, oneshot},
 	tracing::debug,
 };
 
-mod config;
-pub use config::{Config, ConfigBuilder, ConfigFile, Subscription};
+pub use crate::config::{Config, ConfigBuilder, ConfigFile, Subscription};
+
+mod builder;
+pub use builder::{AuthPolicy, ServerBuilder};
+
+mod client;
+use client::Clients;
+pub use client::{ClientId, ClientInfo};
+
+mod dedup;
+use dedup::Deduplicator;
+
+mod filter;
+pub use filter::Filter;
+
+mod access;
+pub use access::Access;
+
+mod handler;
+use handler::{Callback, Handlers};
+pub use {
+	async_trait::async_trait,
+	handler::{EventHandler, ParseError},
+};
+
+mod health;
+
+mod metrics;
+use metrics::Metrics;
+
+mod read;
+mod render;
+
+#[cfg(feature = "websocket")]
+mod websocket;
+
+mod sink;
+pub use sink::{FileSink, Sink, StdoutSink, UdpSink, WebhookSink};
+
+mod stream;
+pub use stream::EventStream;
+
+mod subscriber;
+use subscriber::Subscribers;
+pub use subscriber::{Backpressure, Receiver, SubscriberStats};
+
+#[cfg(test)]
+mod test_util;
+
+#[cfg(not(feature = "gokz"))]
+type SteamId = String;
+
+#[cfg(feature = "gokz")]
+type SteamId = gokz_rs::SteamID;
 
 /// [`axum::Server`] wrapper to listen for GSI events in a background task.
 ///
 /// You can use the [`broadcast::Receiver`] that's returned by the [`Server::start`] method or call
 /// [`Server::subscribe`] to receive these events.
+///
+/// A single server can receive events from multiple game clients at once. Events are attributed to
+/// the client that sent them via [`ClientId`], which can be used to subscribe to a single client
+/// or to query its latest state. Since [`Server::start`] consumes the server, clone it beforehand
+/// to keep a handle for this.
+#[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct Server {
 	/// The config for this server.
 	config: Config,
 
+	/// The address of the interface to listen on.
+	ip: IpAddr,
+
+	/// The path CS:GO sends events to.
+	route: String,
+
+	/// Who is allowed to send events.
+	auth: AuthPolicy,
+
+	/// Whether to keep track of each game client's latest state.
+	track_clients: bool,
+
+	/// Who can reach the ingest route.
+	ingest_access: Access,
+
+	/// Who can reach the read routes.
+	read_access: Access,
+
+	/// The token readers need to send, if any.
+	reader_token: Option<String>,
+
+	/// The origins allowed to access the read routes from a browser, if CORS is enabled.
+	cors_origins: Option<Vec<String>>,
+
+	/// The path of the health route, if enabled.
+	healthz_route: Option<String>,
+
+	/// The path of the readiness route, if enabled.
+	readyz_route: Option<String>,
+
+	/// The path of the Prometheus metrics route, if enabled.
+	metrics_route: Option<String>,
+
+	/// The path of the `GET` route serving the latest event, if enabled.
+	state_route: Option<String>,
+
+	/// The path of the Server-Sent Events route, if enabled.
+	sse_route: Option<String>,
+
+	/// The path of the WebSocket route, if enabled.
+	#[cfg(feature = "websocket")]
+	websocket_route: Option<String>,
+
 	/// [`broadcast::Sender`] for passing along incoming events.
 	event_emitter: broadcast::Sender<crate::Event>,
+
+	/// Filters out payloads CS:GO sent more than once.
+	deduplicator: Arc<Deduplicator>,
+
+	/// The game clients that have sent events to this server.
+	clients: Arc<Clients>,
+
+	/// The last event received from any client.
+	latest: Arc<RwLock<Option<crate::Event>>>,
+
+	/// Counters describing what the server received.
+	metrics: Arc<Metrics>,
+
+	/// Subscribers that only receive some events.
+	subscribers: Arc<Subscribers>,
+
+	/// Callbacks registered via [`Server::register`].
+	handlers: Arc<Handlers>,
+
+	/// The message buffer for each channel.
+	capacity: usize,
 }
 
 impl Server {
+	/// The default message buffer for [`ServerBuilder::capacity`].
+	pub const DEFAULT_CAPACITY: usize = 64;
+	/// The default window for [`ServerBuilder::dedup_window`].
+	pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(10);
+
 	/// Constructs a new [`Server`] with a message buffer of `MESSAGES` and the given conifg.
 	///
-	/// The returned [`broadcast::Receiver`] can be used to listen to incoming events.
+	/// This is a shorthand for `Server::builder().capacity(MESSAGES).build(config)`.
 	#[allow(clippy::new_ret_no_self)]
 	pub fn new<const MESSAGES: usize>(config: Config) -> Self {
-		let (event_emitter, _) = broadcast::channel(MESSAGES);
-		Self { config, event_emitter }
+		Self::builder().capacity(MESSAGES).build(config)
+	}
+
+	/// Constructs a default builder for [`Server`].
+	pub fn builder() -> ServerBuilder {
+		ServerBuilder::default()
+	}
+
+	/// Sets the address of the interface to listen on. Defaults to `127.0.0.1`.
+	///
+	/// This is a shorthand for [`ServerBuilder::listen_on`] on an already built server. Like any
+	/// other field, the address is copied when the server is cloned, so this only affects the
+	/// returned handle.
+	pub fn listen_on(mut self, ip: impl Into<IpAddr>) -> Self {
+		self.ip = ip.into();
+		self
+	}
+
+	/// Sets how long received payloads are remembered to detect retried deliveries. Defaults to
+	/// [`Server::DEFAULT_DEDUP_WINDOW`].
+	///
+	/// This is a shorthand for [`ServerBuilder::dedup_window`] on an already built server. The
+	/// deduplicator is shared between clones, so the new window applies to all of them.
+	pub fn dedup_window(self, window: impl Into<Duration>) -> Self {
+		self.deduplicator.set_window(window.into());
+		self
+	}
+
+	/// The URI CS:GO should send events to.
+	///
+	/// If the server listens on all interfaces, `127.0.0.1` is used as the host.
+	pub fn uri(&self) -> String {
+		let ip = match self.ip {
+			ip if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+			ip => ip,
+		};
+
+		let addr = SocketAddr::new(ip, self.config.service_port);
+		let route = self.route.trim_start_matches('/');
+
+		format!("http://{addr}/{route}")
+	}
+
+	/// Installs the server's config into the given `path`, pointing CS:GO at [`Server::uri`].
+	///
+	/// See [`Config::install`].
+	pub fn install(&self, path: impl Into<PathBuf>) -> Result<ConfigFile> {
+		self.config.install_with_uri(path, self.uri())
 	}
 
 	/// Get an additional [`broadcast::Receiver`] handle for events emitted by this server.
@@ -42,6 +227,209 @@ impl Server {
 		self.event_emitter.subscribe()
 	}
 
+	/// Get an [`EventStream`] of events emitted by this server.
+	pub fn stream(&self) -> EventStream {
+		EventStream::new(self.subscribe())
+	}
+
+	/// Get a [`broadcast::Receiver`] that only receives events passing the given [`Filter`].
+	///
+	/// Events that don't pass the filter are never sent to the receiver, so it doesn't wake up
+	/// for them.
+	pub fn subscribe_filtered(&self, filter: Filter) -> broadcast::Receiver<crate::Event> {
+		self.subscribers.subscribe(filter, self.capacity)
+	}
+
+	/// Get a [`Receiver`] that only receives events passing the given [`Filter`] and handles
+	/// falling behind according to the given [`Backpressure`] policy.
+	///
+	/// Unlike a [`broadcast::Receiver`], the returned [`Receiver`] keeps track of how many events
+	/// it missed.
+	pub fn subscribe_with(&self, filter: Filter, policy: Backpressure) -> Receiver {
+		self.subscribers
+			.subscribe_with(filter, policy, self.capacity)
+	}
+
+	/// Delivery statistics of all subscribers obtained via [`Server::subscribe_filtered`] and
+	/// [`Server::subscribe_with`].
+	///
+	/// Missed events are only counted for subscribers obtained via [`Server::subscribe_with`].
+	pub fn subscribers(&self) -> Vec<SubscriberStats> {
+		self.subscribers.stats()
+	}
+
+	/// Get a [`broadcast::Receiver`] for events sent by a single game client.
+	///
+	/// The client doesn't need to have connected yet.
+	pub fn subscribe_client(
+		&self,
+		client: impl Into<ClientId>,
+	) -> broadcast::Receiver<crate::Event> {
+		self.clients.subscribe(client.into())
+	}
+
+	/// Registers an [`EventHandler`] that is called for everything this server receives.
+	///
+	/// Each handler runs in its own background task that lives as long as the server. Calls to a
+	/// handler are made in the order the server received things, one after another. If a handler
+	/// can't keep up, the oldest calls are dropped, like with [`Backpressure::DropOldest`]. A
+	/// panicking handler is logged and called again for the next event.
+	///
+	/// [`EventHandler::on_connect`] and [`EventHandler::on_disconnect`] are only called if the
+	/// server [keeps track of clients](ServerBuilder::track_clients).
+	///
+	/// # Panics
+	///
+	/// This function panics if called outside of a tokio runtime.
+	pub fn register(&self, handler: impl EventHandler) {
+		self.register_with(handler, Backpressure::DropOldest);
+	}
+
+	/// Registers an [`EventHandler`] with the given [`Backpressure`] policy.
+	///
+	/// With [`Backpressure::Coalesce`], only the latest queued [`EventHandler::on_event`] call is
+	/// made, but every other call still is. See [`Server::register`].
+	///
+	/// # Panics
+	///
+	/// This function panics if called outside of a tokio runtime.
+	pub fn register_with(&self, handler: impl EventHandler, policy: Backpressure) {
+		self.handlers
+			.register(Arc::new(handler), policy, self.capacity);
+
+		if self.track_clients {
+			let timeout = self.config.heartbeat + self.config.buffer;
+			Handlers::watch(&self.handlers, Arc::clone(&self.clients), timeout);
+		}
+	}
+
+	/// Forwards every event this server receives to the given [`Sink`].
+	///
+	/// Sinks are fed like handlers registered via [`Server::register`], with a queue that drops
+	/// the oldest events if the sink can't keep up. See [`Sink`].
+	///
+	/// # Panics
+	///
+	/// This function panics if called outside of a tokio runtime.
+	pub fn add_sink(&self, sink: impl Sink) {
+		self.register_with(sink::SinkHandler::new(sink), Backpressure::DropOldest);
+	}
+
+	/// The last event received from any game client.
+	pub fn latest(&self) -> Option<crate::Event> {
+		self.latest
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.clone()
+	}
+
+	/// Whether the server has passed on an event within the last heartbeat window.
+	///
+	/// CS:GO sends an event at least every [`Config::heartbeat`], so if there wasn't one for
+	/// longer than that (plus [`Config::buffer`], which the game waits before sending), the
+	/// connection to the game was most likely lost.
+	pub fn is_ready(&self) -> bool {
+		let window = self.config.heartbeat + self.config.buffer;
+
+		self.metrics
+			.last_event()
+			.is_some_and(|last_event| last_event.elapsed() <= window)
+	}
+
+	/// All game clients that have sent events to this server.
+	pub fn clients(&self) -> Vec<ClientInfo> {
+		self.clients.list()
+	}
+
+	/// The last event sent by the given game client.
+	pub fn client_state(&self, client: impl Into<ClientId>) -> Option<crate::Event> {
+		self.clients.latest(&client.into())
+	}
+
+	/// Builds a [`Router`] handling events sent to the server's route.
+	///
+	/// This can be used to receive events as part of an existing axum application instead of
+	/// calling [`Server::start`]. The router shares its state with this server, so events it
+	/// receives are sent to this server's subscribers.
+	///
+	/// To record the address events were sent from, serve the application using
+	/// [`Router::into_make_service_with_connect_info`] with [`SocketAddr`]. This is required for
+	/// [`Access::LocalOnly`], which rejects every request whose address isn't known.
+	///
+	/// ```no_run
+	/// use {
+	///     axum::{routing::get, Router},
+	///     schnose_gsi::{Config, Server},
+	/// };
+	///
+	/// # async fn run() {
+	/// let config = Config::builder().build("my-overlay", 8080_u16);
+	/// let server = Server::builder().build(config);
+	/// let mut events = server.subscribe();
+	///
+	/// let app = Router::new()
+	///     .route("/overlay", get(|| async { "Hello!" }))
+	///     .nest("/gsi", server.router());
+	///
+	/// axum::Server::bind(&"127.0.0.1:8080".parse().unwrap())
+	///     .serve(app.into_make_service())
+	///     .await
+	///     .unwrap();
+	/// # }
+	/// ```
+	///
+	/// Don't forget to point CS:GO at the nested path, e.g. via [`Config::install_with_uri`].
+	pub fn router<S>(&self) -> Router<S>
+	where
+		S: Clone + Send + Sync + 'static, {
+		let server = Arc::new(self.clone());
+
+		let mut ingest = Router::new()
+			.route(&self.route, post(Self::handler))
+			.route_layer(from_fn_with_state(Arc::clone(&server), access::ingest));
+
+		if let Some(route) = self.healthz_route.as_deref() {
+			ingest = ingest.route(route, get(health::healthz));
+		}
+
+		if let Some(route) = self.readyz_route.as_deref() {
+			ingest = ingest.route(route, get(health::readyz));
+		}
+
+		#[cfg_attr(not(feature = "websocket"), allow(unused_mut))]
+		let mut read_routes = vec![
+			(self.state_route.as_deref(), get(read::state)),
+			(self.sse_route.as_deref(), get(read::events)),
+			(self.metrics_route.as_deref(), get(metrics::handler)),
+		];
+
+		#[cfg(feature = "websocket")]
+		read_routes.push((self.websocket_route.as_deref(), get(websocket::handler)));
+
+		let read_routes = read_routes
+			.into_iter()
+			.filter_map(|(route, handler)| Some((route?, handler)))
+			.collect::<Vec<_>>();
+
+		if read_routes.is_empty() {
+			return ingest.with_state(server);
+		}
+
+		let mut read = Router::new();
+
+		for (route, handler) in read_routes {
+			read = read.route(route, handler);
+		}
+
+		read = read.route_layer(from_fn_with_state(Arc::clone(&server), access::read));
+
+		if let Some(origins) = self.cors_origins.as_deref() {
+			read = read.layer(access::cors(origins));
+		}
+
+		ingest.merge(read).with_state(server)
+	}
+
 	/// Starts a background task with the server running.
 	///
 	/// You can use the returned [`KillSignal`] to stop the server later.
@@ -49,13 +437,11 @@ impl Server {
 		let event_listener = self.subscribe();
 		let (kill_signal, rx) = oneshot::channel();
 
-		let addr = SocketAddr::from(([127, 0, 0, 1], self.config.service_port));
-		let router = Router::new()
-			.route("/", post(Self::handler))
-			.with_state(Arc::new(self));
+		let addr = SocketAddr::new(self.ip, self.config.service_port);
+		let router = self.router::<()>();
 
 		let server = axum::Server::bind(&addr)
-			.serve(router.into_make_service())
+			.serve(router.into_make_service_with_connect_info::<SocketAddr>())
 			.with_graceful_shutdown(async {
 				_ = rx.await;
 			});
@@ -67,12 +453,95 @@ impl Server {
 
 	async fn handler(
 		State(state): State<Arc<Self>>,
-		Json(event): Json<crate::Event>,
+		remote_addr: Option<ConnectInfo<SocketAddr>>,
+		payload: Bytes,
 	) -> StatusCode {
 		debug!("received message");
-		_ = state.event_emitter.send(event);
+		state.metrics.payload(payload.len());
+
+		let remote_addr = remote_addr.map(|ConnectInfo(addr)| addr);
+
+		if !state.auth.allows_payload(&state.config, &payload) {
+			debug!("rejecting unauthorized event");
+			state.metrics.rejected_auth();
+			return StatusCode::UNAUTHORIZED;
+		}
+
+		let mut event = match serde_json::from_slice::<crate::Event>(&payload) {
+			Ok(event) => event,
+			Err(error) => {
+				debug!(%error, "failed to parse event");
+				state.metrics.parse_error();
+
+				state
+					.parse_error(error.to_string(), &payload, remote_addr)
+					.await;
+
+				return StatusCode::BAD_REQUEST;
+			}
+		};
+
+		event.delivery = Some(Delivery { remote_addr, ..Delivery::now(&payload) });
+
+		state.publish(event).await;
 		StatusCode::OK
 	}
+
+	/// Passes a payload that couldn't be parsed on to all [`EventHandler`]s.
+	async fn parse_error(&self, message: String, payload: &[u8], remote_addr: Option<SocketAddr>) {
+		if self.handlers.is_empty() {
+			return;
+		}
+
+		let error = ParseError { message, payload: payload.to_vec(), remote_addr };
+
+		self.handlers
+			.dispatch(Callback::ParseError(error))
+			.await;
+	}
+
+	/// Passes a received event on to all subscribers, unless it is a duplicate.
+	async fn publish(&self, event: crate::Event) {
+		if self.deduplicator.is_duplicate(&event) {
+			debug!("dropping duplicate delivery");
+			self.metrics.duplicate();
+			return;
+		}
+
+		self.metrics.event();
+
+		let previous = ClientId::of(&event)
+			.filter(|_| self.track_clients)
+			.and_then(|client| self.clients.latest(&client));
+
+		self.subscribers
+			.dispatch(&event, previous.as_ref())
+			.await;
+
+		let connected = self
+			.track_clients
+			.then(|| self.clients.update(&event))
+			.flatten();
+
+		*self
+			.latest
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(event.clone());
+
+		if !self.handlers.is_empty() {
+			if let Some(client) = connected {
+				self.handlers
+					.dispatch(Callback::Connect(client))
+					.await;
+			}
+
+			self.handlers
+				.dispatch(Callback::Event(Box::new(event.clone())))
+				.await;
+		}
+
+		_ = self.event_emitter.send(event);
+	}
 }
 
 /// Signal for shutting down the [`Server`] you got this from.
@@ -0,0 +1,205 @@
+//! Read routes serving the latest state, e.g. to overlays or `curl`.
+
+use {
+	super::{
+		render::{Params, Sections},
+		Server,
+	},
+	axum::{
+		extract::{Query, State},
+		http::{header, StatusCode},
+		response::{
+			sse::{self, KeepAlive, Sse},
+			IntoResponse, Response,
+		},
+	},
+	futures_util::{stream, Stream},
+	std::{convert::Infallible, sync::Arc},
+	tokio::sync::broadcast::error::RecvError,
+};
+
+/// `GET` handler responding with the latest event as JSON.
+///
+/// Responds with `204 No Content` if no event has been received yet.
+pub(crate) async fn state(
+	State(server): State<Arc<Server>>,
+	Query(params): Query<Params>,
+) -> Response {
+	let Some(event) = server.latest() else {
+		return StatusCode::NO_CONTENT.into_response();
+	};
+
+	match Sections::new(&params).render(&event) {
+		Some(json) => ([(header::CONTENT_TYPE, "application/json")], json).into_response(),
+		None => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+	}
+}
+
+/// `GET` handler streaming events as Server-Sent Events.
+///
+/// Starts with the latest event, followed by every event in which the requested sections changed.
+pub(crate) async fn events(
+	State(server): State<Arc<Server>>,
+	Query(params): Query<Params>,
+) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+	let state = EventsState {
+		server: Arc::clone(&server),
+		sections: Sections::new(&params),
+		initial: server.latest(),
+		events: server.subscribe(),
+		last_sent: None,
+	};
+
+	let stream = stream::unfold(state, |mut state| async move {
+		let message = state.next().await?;
+		Some((Ok(sse::Event::default().data(message)), state))
+	});
+
+	Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// The state of a single Server-Sent Events stream.
+struct EventsState {
+	server: Arc<Server>,
+	sections: Sections,
+
+	/// The latest event at the time of connecting, until it has been sent.
+	initial: Option<crate::Event>,
+
+	events: tokio::sync::broadcast::Receiver<crate::Event>,
+
+	/// The last message sent to this stream.
+	last_sent: Option<String>,
+}
+
+impl EventsState {
+	/// Waits for the next message to send.
+	///
+	/// Returns [`None`] once the server has been closed.
+	async fn next(&mut self) -> Option<String> {
+		loop {
+			let event = match self.initial.take() {
+				Some(event) => event,
+				None => match self.events.recv().await {
+					Ok(event) => event,
+					Err(RecvError::Lagged(missed)) => {
+						self.server.metrics.missed(missed);
+						continue;
+					}
+					Err(RecvError::Closed) => return None,
+				},
+			};
+
+			let Some(message) = self.sections.render(&event) else {
+				continue;
+			};
+
+			if self.last_sent.as_ref() == Some(&message) {
+				continue;
+			}
+
+			self.last_sent = Some(message.clone());
+			return Some(message);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::{
+			csgo::test_util as fixtures,
+			server::{test_util, Config},
+		},
+		serde_json::{json, Value},
+	};
+
+	fn server() -> Server {
+		Server::builder()
+			.state("/state")
+			.sse("/events")
+			.reader_token("secret")
+			.build(
+				Config::builder()
+					.auth("token", "game")
+					.build("test", 8080_u16),
+			)
+	}
+
+	fn event() -> crate::Event {
+		fixtures::from_json(json!({
+			"provider": fixtures::provider(1),
+			"auth": { "token": "game" },
+			"map": fixtures::map("de_mirage", 3),
+		}))
+	}
+
+	#[tokio::test]
+	async fn state() {
+		let server = server();
+
+		assert_eq!(
+			test_util::status(&server, test_util::get("/state")).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_eq!(
+			test_util::status(&server, test_util::get_with_token("/state", "secret")).await,
+			StatusCode::NO_CONTENT
+		);
+
+		server.publish(event()).await;
+
+		let response = test_util::send(&server, test_util::get("/state?token=secret")).await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+		let state = serde_json::from_str::<Value>(&test_util::first_chunk(response).await)
+			.expect("valid JSON");
+		assert_eq!(state["map"]["name"], "de_mirage");
+		assert_eq!(state["provider"]["timestamp"], 1);
+		assert!(state.get("auth").is_none());
+	}
+
+	#[tokio::test]
+	async fn state_sections() {
+		let server = server();
+		server.publish(event()).await;
+
+		let request = test_util::get_with_token("/state?sections=map,%20round", "secret");
+		let response = test_util::send(&server, request).await;
+		let state = serde_json::from_str::<Value>(&test_util::first_chunk(response).await)
+			.expect("valid JSON");
+
+		// Sections that aren't part of the event are still sent, as `null`.
+		assert_eq!(state, json!({ "map": state["map"], "round": null }));
+		assert_eq!(state["map"]["name"], "de_mirage");
+	}
+
+	#[tokio::test]
+	async fn events() {
+		let server = server();
+
+		assert_eq!(
+			test_util::status(&server, test_util::get("/events?sections=map")).await,
+			StatusCode::UNAUTHORIZED
+		);
+
+		server.publish(event()).await;
+
+		let request = test_util::get_with_token("/events?sections=map", "secret");
+		let response = test_util::send(&server, request).await;
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+		let message = test_util::first_chunk(response).await;
+		let data = message
+			.strip_prefix("data:")
+			.expect("message has data")
+			.trim_end();
+
+		let state = serde_json::from_str::<Value>(data).expect("valid JSON");
+		assert_eq!(state["map"]["name"], "de_mirage");
+		assert_eq!(state.as_object().map(|state| state.len()), Some(1));
+	}
+}
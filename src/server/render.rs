@@ -0,0 +1,46 @@
+//! Rendering events for read routes like the WebSocket route or `GET /state`.
+
+use {crate::Event, serde::Deserialize, serde_json::Value, std::collections::HashSet};
+
+/// Query parameters of the read routes.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Params {
+	/// Comma separated list of top-level sections to send, e.g. `map,round`.
+	sections: Option<String>,
+}
+
+/// The top-level sections of an event a reader is interested in.
+///
+/// Defaults to all sections.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Sections(Option<HashSet<String>>);
+
+impl Sections {
+	pub(crate) fn new(params: &Params) -> Self {
+		Self(params.sections.as_deref().map(|sections| {
+			sections
+				.split(',')
+				.map(str::trim)
+				.filter(|section| !section.is_empty())
+				.map(String::from)
+				.collect()
+		}))
+	}
+
+	/// Serializes `event`, keeping only the requested sections.
+	///
+	/// Auth values are never sent, since they contain the secrets the game uses to authenticate.
+	pub(crate) fn render(&self, event: &Event) -> Option<String> {
+		let Value::Object(mut event) = serde_json::to_value(event).ok()? else {
+			return None;
+		};
+
+		event.remove("auth");
+
+		if let Some(sections) = self.0.as_ref() {
+			event.retain(|section, _| sections.contains(section));
+		}
+
+		serde_json::to_string(&event).ok()
+	}
+}
@@ -0,0 +1,235 @@
+use {
+	super::{async_trait, to_json, Sink},
+	crate::{Error, Event, Result},
+	std::{io, path::PathBuf},
+	tokio::{
+		fs::{self, File, OpenOptions},
+		io::AsyncWriteExt,
+	},
+};
+
+/// Appends events to a file as JSON lines, e.g. to replay them later.
+///
+/// Once the file grows beyond [`FileSink::max_size`], it is rotated: `events.jsonl` is renamed to
+/// `events.jsonl.1`, `events.jsonl.1` to `events.jsonl.2` and so on, keeping at most
+/// [`FileSink::max_files`] old files.
+#[derive(Debug)]
+pub struct FileSink {
+	/// The file events are written to.
+	path: PathBuf,
+
+	/// The size at which the file is rotated, if any.
+	max_size: Option<u64>,
+
+	/// How many rotated files are kept.
+	max_files: usize,
+
+	/// The open file and its size, opened on the first event and after every rotation.
+	file: Option<(File, u64)>,
+}
+
+impl FileSink {
+	/// The default for [`FileSink::max_files`].
+	pub const DEFAULT_MAX_FILES: usize = 5;
+
+	/// Constructs a new [`FileSink`] appending events to the file at `path`.
+	///
+	/// The file is created if it doesn't exist yet.
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into(), max_size: None, max_files: Self::DEFAULT_MAX_FILES, file: None }
+	}
+
+	/// Rotates the file once it grows beyond `bytes`. Files aren't rotated by default.
+	pub fn max_size(mut self, bytes: u64) -> Self {
+		self.max_size = Some(bytes);
+		self
+	}
+
+	/// Sets how many rotated files are kept. Defaults to [`FileSink::DEFAULT_MAX_FILES`].
+	///
+	/// With `0`, the file is truncated instead of being rotated.
+	pub fn max_files(mut self, files: usize) -> Self {
+		self.max_files = files;
+		self
+	}
+
+	/// The path of the `n`th rotated file.
+	fn rotated(&self, n: usize) -> PathBuf {
+		let mut path = self.path.clone().into_os_string();
+		path.push(format!(".{n}"));
+		path.into()
+	}
+
+	async fn file(&mut self) -> io::Result<&mut (File, u64)> {
+		if let Some(ref mut file) = self.file {
+			return Ok(file);
+		}
+
+		let file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.path)
+			.await?;
+
+		let size = file.metadata().await?.len();
+
+		Ok(self.file.insert((file, size)))
+	}
+
+	async fn rotate(&mut self) -> io::Result<()> {
+		self.file = None;
+
+		if self.max_files == 0 {
+			return fs::remove_file(&self.path).await;
+		}
+
+		for n in (1..self.max_files).rev() {
+			match fs::rename(self.rotated(n), self.rotated(n + 1)).await {
+				Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+				_ => {}
+			}
+		}
+
+		fs::rename(&self.path, self.rotated(1)).await
+	}
+
+	async fn write(&mut self, line: &str) -> io::Result<()> {
+		let (file, size) = self.file().await?;
+
+		file.write_all(line.as_bytes()).await?;
+		file.flush().await?;
+		*size += line.len() as u64;
+
+		let size = *size;
+
+		if self
+			.max_size
+			.is_some_and(|max_size| size >= max_size)
+		{
+			self.rotate().await?;
+		}
+
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl Sink for FileSink {
+	async fn send(&mut self, event: &Event) -> Result<()> {
+		let mut line = to_json(event)?;
+		line.push('\n');
+
+		self.write(&line)
+			.await
+			.map_err(|error| Error::Sink(error.to_string()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::{
+			super::{parse_forwarded, test_event},
+			*,
+		},
+		std::path::Path,
+	};
+
+	/// A fresh directory for a single test.
+	fn temp_dir(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("schnose_gsi-{}-{name}", std::process::id()));
+
+		_ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(&dir).expect("temp dir can be created");
+
+		dir
+	}
+
+	/// The timestamps of the events in the file at `path`.
+	fn read(path: impl AsRef<Path>) -> Vec<u64> {
+		std::fs::read_to_string(path)
+			.expect("file exists")
+			.lines()
+			.map(parse_forwarded)
+			.collect()
+	}
+
+	fn rotated(path: &Path, n: usize) -> PathBuf {
+		PathBuf::from(format!("{}.{n}", path.display()))
+	}
+
+	#[tokio::test]
+	async fn appends_lines() {
+		let dir = temp_dir("append");
+		let path = dir.join("events.jsonl");
+		let mut sink = FileSink::new(&path);
+
+		for timestamp in 1..=3 {
+			sink.send(&test_event(timestamp))
+				.await
+				.expect("event is written");
+		}
+
+		assert_eq!(read(&path), [1, 2, 3]);
+
+		// Reopening appends to the existing file.
+		let mut sink = FileSink::new(&path);
+		sink.send(&test_event(4))
+			.await
+			.expect("event is written");
+
+		assert_eq!(read(&path), [1, 2, 3, 4]);
+
+		_ = std::fs::remove_dir_all(dir);
+	}
+
+	#[tokio::test]
+	async fn rotates_files() {
+		let dir = temp_dir("rotate");
+		let path = dir.join("events.jsonl");
+		let line = to_json(&test_event(1))
+			.expect("serializable")
+			.len() as u64
+			+ 1;
+		let mut sink = FileSink::new(&path)
+			.max_size(line * 2)
+			.max_files(2);
+
+		for timestamp in 1..=7 {
+			sink.send(&test_event(timestamp))
+				.await
+				.expect("event is written");
+		}
+
+		assert_eq!(read(&path), [7]);
+		assert_eq!(read(rotated(&path, 1)), [5, 6]);
+		assert_eq!(read(rotated(&path, 2)), [3, 4]);
+		assert!(!rotated(&path, 3).exists());
+
+		_ = std::fs::remove_dir_all(dir);
+	}
+
+	#[tokio::test]
+	async fn zero_max_files_truncates() {
+		let dir = temp_dir("truncate");
+		let path = dir.join("events.jsonl");
+		let line = to_json(&test_event(1))
+			.expect("serializable")
+			.len() as u64
+			+ 1;
+		let mut sink = FileSink::new(&path)
+			.max_size(line * 2)
+			.max_files(0);
+
+		for timestamp in 1..=3 {
+			sink.send(&test_event(timestamp))
+				.await
+				.expect("event is written");
+		}
+
+		assert_eq!(read(&path), [3]);
+		assert!(!rotated(&path, 1).exists());
+
+		_ = std::fs::remove_dir_all(dir);
+	}
+}
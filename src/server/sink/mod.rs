@@ -0,0 +1,85 @@
+//! Forwarding events to other tools, see [`Sink`].
+
+use {
+	super::{async_trait, render::Sections, EventHandler},
+	crate::{Error, Event, Result},
+	tokio::sync::Mutex,
+	tracing::error,
+};
+
+mod file;
+pub use file::FileSink;
+
+mod stdout;
+pub use stdout::StdoutSink;
+
+mod udp;
+pub use udp::UdpSink;
+
+mod webhook;
+pub use webhook::WebhookSink;
+
+/// A destination events are forwarded to, added via [`Server::add_sink`](super::Server::add_sink).
+///
+/// Sinks receive events in the order the server received them. The next event is only sent once
+/// the sink is done with the previous one. Errors are logged and don't stop the sink from
+/// receiving further events.
+///
+/// A sink never slows down the server. Events are queued for every sink and once its queue is
+/// full, the oldest events are dropped and counted in the server's
+/// [metrics](super::ServerBuilder::metrics).
+#[async_trait]
+pub trait Sink: Send + 'static {
+	/// Forwards a single event.
+	async fn send(&mut self, event: &Event) -> Result<()>;
+}
+
+/// Serializes `event` as a single line of JSON, without auth values.
+fn to_json(event: &Event) -> Result<String> {
+	Sections::default()
+		.render(event)
+		.ok_or_else(|| Error::Sink(String::from("failed to serialize event")))
+}
+
+/// Feeds a [`Sink`] as an [`EventHandler`].
+pub(crate) struct SinkHandler<S> {
+	sink: Mutex<S>,
+}
+
+impl<S: Sink> SinkHandler<S> {
+	pub(crate) fn new(sink: S) -> Self {
+		Self { sink: Mutex::new(sink) }
+	}
+}
+
+#[async_trait]
+impl<S: Sink> EventHandler for SinkHandler<S> {
+	async fn on_event(&self, event: &Event) {
+		if let Err(error) = self.sink.lock().await.send(event).await {
+			error!(%error, "failed to forward event");
+		}
+	}
+}
+
+/// An event with auth values, which sinks must never forward.
+#[cfg(test)]
+fn test_event(timestamp: u64) -> Event {
+	use crate::csgo::test_util;
+
+	test_util::from_json(serde_json::json!({
+		"provider": test_util::provider(timestamp),
+		"auth": { "token": "secret" },
+	}))
+}
+
+/// Parses a forwarded event, checking that auth values were stripped.
+#[cfg(test)]
+fn parse_forwarded(json: &str) -> u64 {
+	let event = serde_json::from_str::<serde_json::Value>(json).expect("valid JSON");
+
+	assert!(event.get("auth").is_none());
+
+	event["provider"]["timestamp"]
+		.as_u64()
+		.expect("event has a timestamp")
+}
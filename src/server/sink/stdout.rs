@@ -0,0 +1,29 @@
+use {
+	super::{async_trait, to_json, Sink},
+	crate::{Error, Event, Result},
+	tokio::io::{self, AsyncWriteExt},
+};
+
+/// Writes events to stdout as JSON lines, e.g. to pipe them into another program.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StdoutSink;
+
+#[async_trait]
+impl Sink for StdoutSink {
+	async fn send(&mut self, event: &Event) -> Result<()> {
+		let mut line = to_json(event)?;
+		line.push('\n');
+
+		let mut stdout = io::stdout();
+
+		stdout
+			.write_all(line.as_bytes())
+			.await
+			.map_err(|error| Error::Sink(error.to_string()))?;
+
+		stdout
+			.flush()
+			.await
+			.map_err(|error| Error::Sink(error.to_string()))
+	}
+}
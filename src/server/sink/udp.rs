@@ -0,0 +1,100 @@
+use {
+	super::{async_trait, to_json, Sink},
+	crate::{Error, Event, Result},
+	std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+	tokio::net::UdpSocket,
+};
+
+/// Sends every event as a single JSON datagram, for low-latency consumers on the same machine or
+/// network.
+///
+/// UDP is unreliable, so events may be lost without an error. Events that don't fit into a single
+/// datagram (about 64 KiB) can't be sent.
+#[derive(Debug)]
+pub struct UdpSink {
+	/// Where events are sent to.
+	target: SocketAddr,
+
+	/// Bound on the first event.
+	socket: Option<UdpSocket>,
+}
+
+impl UdpSink {
+	/// Constructs a new [`UdpSink`] sending events to `target`, e.g. `127.0.0.1:9000`.
+	pub fn new(target: impl Into<SocketAddr>) -> Self {
+		Self { target: target.into(), socket: None }
+	}
+
+	async fn socket(&mut self) -> Result<&UdpSocket> {
+		if let Some(ref socket) = self.socket {
+			return Ok(socket);
+		}
+
+		let ip = match self.target {
+			SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+			SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+		};
+
+		let socket = UdpSocket::bind(SocketAddr::new(ip, 0))
+			.await
+			.map_err(|error| Error::Sink(error.to_string()))?;
+
+		socket
+			.connect(self.target)
+			.await
+			.map_err(|error| Error::Sink(error.to_string()))?;
+
+		Ok(self.socket.insert(socket))
+	}
+}
+
+#[async_trait]
+impl Sink for UdpSink {
+	async fn send(&mut self, event: &Event) -> Result<()> {
+		let json = to_json(event)?;
+
+		self.socket()
+			.await?
+			.send(json.as_bytes())
+			.await
+			.map_err(|error| Error::Sink(error.to_string()))?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::{
+			super::{parse_forwarded, test_event},
+			*,
+		},
+		std::time::Duration,
+		tokio::time::timeout,
+	};
+
+	#[tokio::test]
+	async fn sends_datagrams() {
+		let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+			.await
+			.expect("socket can be bound");
+
+		let mut sink = UdpSink::new(receiver.local_addr().expect("socket is bound"));
+		let mut buf = vec![0; 64 * 1024];
+
+		for timestamp in 1..=2 {
+			sink.send(&test_event(timestamp))
+				.await
+				.expect("event is sent");
+
+			let len = timeout(Duration::from_secs(5), receiver.recv(&mut buf))
+				.await
+				.expect("datagram arrives")
+				.expect("datagram can be received");
+
+			let json = std::str::from_utf8(&buf[..len]).expect("valid UTF-8");
+			assert_eq!(parse_forwarded(json), timestamp);
+		}
+	}
+}
@@ -0,0 +1,268 @@
+use {
+	super::{async_trait, to_json, Sink},
+	crate::{Error, Event, Result},
+	hyper::{client::HttpConnector, header::CONTENT_TYPE, Body, Client, Request, StatusCode, Uri},
+	std::{result::Result as StdResult, time::Duration},
+	tracing::debug,
+};
+
+/// `POST`s every event as JSON to a URL.
+///
+/// Failed deliveries (connection errors, timeouts, `429 Too Many Requests` and `5xx` responses)
+/// are retried with exponential backoff. Later events wait until the current one was delivered or
+/// given up on. Only `http://` URLs are supported.
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+	uri: Uri,
+	client: Client<HttpConnector>,
+
+	/// How often a failed delivery is retried.
+	retries: u32,
+
+	/// How long to wait before the first retry. Doubles with every retry.
+	backoff: Duration,
+
+	/// How long a single delivery attempt may take.
+	timeout: Duration,
+}
+
+impl WebhookSink {
+	/// The default for [`WebhookSink::backoff`].
+	pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+	/// The default for [`WebhookSink::retries`].
+	pub const DEFAULT_RETRIES: u32 = 3;
+	/// The default for [`WebhookSink::timeout`].
+	pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+	/// The longest wait between two retries.
+	pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+	/// Constructs a new [`WebhookSink`] sending events to `uri`, e.g.
+	/// `http://127.0.0.1:9000/events`.
+	///
+	/// Fails if `uri` isn't a valid `http://` URL.
+	pub fn new(uri: impl AsRef<str>) -> Result<Self> {
+		let uri = uri
+			.as_ref()
+			.parse::<Uri>()
+			.map_err(|error| Error::Sink(error.to_string()))?;
+
+		if uri.scheme_str() != Some("http") {
+			return Err(Error::Sink(format!(
+				"unsupported webhook URL `{uri}`, expected `http://`"
+			)));
+		}
+
+		Ok(Self {
+			uri,
+			client: Client::new(),
+			retries: Self::DEFAULT_RETRIES,
+			backoff: Self::DEFAULT_BACKOFF,
+			timeout: Self::DEFAULT_TIMEOUT,
+		})
+	}
+
+	/// Sets how often a failed delivery is retried. Defaults to [`WebhookSink::DEFAULT_RETRIES`].
+	pub fn retries(mut self, retries: u32) -> Self {
+		self.retries = retries;
+		self
+	}
+
+	/// Sets how long to wait before the first retry. Defaults to [`WebhookSink::DEFAULT_BACKOFF`].
+	///
+	/// The wait doubles with every retry, up to [`WebhookSink::MAX_BACKOFF`].
+	pub fn backoff(mut self, backoff: impl Into<Duration>) -> Self {
+		self.backoff = backoff.into();
+		self
+	}
+
+	/// Sets how long a single delivery attempt may take before it is aborted and retried.
+	/// Defaults to [`WebhookSink::DEFAULT_TIMEOUT`].
+	pub fn timeout(mut self, timeout: impl Into<Duration>) -> Self {
+		self.timeout = timeout.into();
+		self
+	}
+
+	/// Makes a single delivery attempt.
+	///
+	/// Returns whether a failed attempt should be retried.
+	async fn post(&self, json: &str) -> StdResult<(), (Error, bool)> {
+		let request = Request::post(self.uri.clone())
+			.header(CONTENT_TYPE, "application/json")
+			.body(Body::from(json.to_owned()))
+			.map_err(|error| (Error::Sink(error.to_string()), false))?;
+
+		let status = match tokio::time::timeout(self.timeout, self.client.request(request)).await {
+			Ok(Ok(response)) => response.status(),
+			Ok(Err(error)) => return Err((Error::Sink(error.to_string()), true)),
+			Err(_) => {
+				let error = format!("webhook didn't respond within {:?}", self.timeout);
+				return Err((Error::Sink(error), true));
+			}
+		};
+
+		if status.is_success() {
+			return Ok(());
+		}
+
+		let retry = status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+
+		Err((Error::Sink(format!("webhook responded with {status}")), retry))
+	}
+}
+
+/// Doubles `backoff`, up to [`WebhookSink::MAX_BACKOFF`].
+fn next_backoff(backoff: Duration) -> Duration {
+	backoff
+		.saturating_mul(2)
+		.min(WebhookSink::MAX_BACKOFF)
+}
+
+#[async_trait]
+impl Sink for WebhookSink {
+	async fn send(&mut self, event: &Event) -> Result<()> {
+		let json = to_json(event)?;
+		let mut backoff = self.backoff.min(Self::MAX_BACKOFF);
+		let mut attempt = 0;
+
+		loop {
+			let error = match self.post(&json).await {
+				Ok(()) => return Ok(()),
+				Err((error, true)) if attempt < self.retries => error,
+				Err((error, _)) => return Err(error),
+			};
+
+			attempt += 1;
+			debug!(%error, attempt, "retrying webhook delivery");
+
+			tokio::time::sleep(backoff).await;
+			backoff = next_backoff(backoff);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::{
+			super::{parse_forwarded, test_event},
+			*,
+		},
+		axum::{extract::State, routing::post, Router},
+		std::{
+			net::SocketAddr,
+			sync::{Arc, Mutex},
+		},
+	};
+
+	/// Received bodies and the statuses to respond with, in order.
+	#[derive(Default)]
+	struct Endpoint {
+		bodies: Mutex<Vec<String>>,
+		statuses: Mutex<Vec<StatusCode>>,
+	}
+
+	/// Serves an endpoint responding with `statuses` in order and `200 OK` once they run out.
+	///
+	/// Every response is delayed by `delay`.
+	async fn serve(statuses: &[StatusCode], delay: Duration) -> (SocketAddr, Arc<Endpoint>) {
+		let endpoint = Arc::new(Endpoint {
+			bodies: Mutex::default(),
+			statuses: Mutex::new(statuses.iter().rev().copied().collect()),
+		});
+
+		let app = Router::new()
+			.route(
+				"/events",
+				post(move |State(endpoint): State<Arc<Endpoint>>, body: String| async move {
+					endpoint.bodies.lock().unwrap().push(body);
+					tokio::time::sleep(delay).await;
+					endpoint
+						.statuses
+						.lock()
+						.unwrap()
+						.pop()
+						.unwrap_or(StatusCode::OK)
+				}),
+			)
+			.with_state(Arc::clone(&endpoint));
+
+		let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+			.serve(app.into_make_service());
+		let addr = server.local_addr();
+
+		tokio::spawn(server);
+
+		(addr, endpoint)
+	}
+
+	fn sink(addr: SocketAddr) -> WebhookSink {
+		WebhookSink::new(format!("http://{addr}/events"))
+			.expect("valid URL")
+			.backoff(Duration::from_millis(1))
+	}
+
+	#[tokio::test]
+	async fn retries_server_errors() {
+		let (addr, endpoint) = serve(&[StatusCode::SERVICE_UNAVAILABLE], Duration::ZERO).await;
+
+		sink(addr)
+			.send(&test_event(1))
+			.await
+			.expect("second attempt succeeds");
+
+		let bodies = endpoint.bodies.lock().unwrap();
+		assert_eq!(bodies.len(), 2);
+		assert!(bodies
+			.iter()
+			.all(|body| parse_forwarded(body) == 1));
+	}
+
+	#[tokio::test]
+	async fn gives_up_after_retries() {
+		let (addr, endpoint) = serve(&[StatusCode::SERVICE_UNAVAILABLE; 3], Duration::ZERO).await;
+
+		assert!(sink(addr)
+			.retries(2)
+			.send(&test_event(1))
+			.await
+			.is_err());
+
+		assert_eq!(endpoint.bodies.lock().unwrap().len(), 3);
+	}
+
+	#[tokio::test]
+	async fn client_errors_are_not_retried() {
+		let (addr, endpoint) = serve(&[StatusCode::BAD_REQUEST], Duration::ZERO).await;
+
+		assert!(sink(addr).send(&test_event(1)).await.is_err());
+		assert_eq!(endpoint.bodies.lock().unwrap().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn slow_responses_time_out() {
+		let (addr, endpoint) = serve(&[], Duration::from_secs(5)).await;
+
+		assert!(sink(addr)
+			.retries(1)
+			.timeout(Duration::from_millis(50))
+			.send(&test_event(1))
+			.await
+			.is_err());
+
+		assert_eq!(endpoint.bodies.lock().unwrap().len(), 2);
+	}
+
+	#[test]
+	fn backoff_is_capped() {
+		assert_eq!(next_backoff(Duration::from_secs(1)), Duration::from_secs(2));
+		assert_eq!(next_backoff(Duration::from_secs(40)), WebhookSink::MAX_BACKOFF);
+		assert_eq!(next_backoff(Duration::MAX), WebhookSink::MAX_BACKOFF);
+	}
+
+	#[test]
+	fn only_http_is_supported() {
+		assert!(WebhookSink::new("http://127.0.0.1:9000/events").is_ok());
+		assert!(WebhookSink::new("https://example.com/events").is_err());
+		assert!(WebhookSink::new("not a url").is_err());
+	}
+}
@@ -0,0 +1,273 @@
+use {
+	super::Receiver,
+	crate::{csgo::GameInfo, Event},
+	futures_util::{future, stream, Stream, StreamExt},
+	std::{
+		fmt,
+		pin::Pin,
+		sync::{
+			atomic::{AtomicU64, Ordering},
+			Arc,
+		},
+		task::{Context, Poll},
+	},
+	tokio::sync::broadcast::{self, error::RecvError},
+};
+
+/// A [`Stream`] of events received by a [`Server`](super::Server).
+///
+/// Unlike a [`broadcast::Receiver`], lagging behind doesn't surface as an error. Missed events are
+/// skipped and counted instead, see [`EventStream::missed`].
+///
+/// ```no_run
+/// use {futures_util::StreamExt, schnose_gsi::Server};
+///
+/// # async fn run(server: Server) {
+/// let mut maps = server
+///     .stream()
+///     .skip_heartbeats()
+///     .only_changes(|event| event.map.as_ref().map(|map| map.name.clone()));
+///
+/// while let Some(event) = maps.next().await {
+///     println!("{:?}", event.map);
+/// }
+/// # }
+/// ```
+pub struct EventStream {
+	inner: Pin<Box<dyn Stream<Item = Event> + Send>>,
+
+	/// How many events were missed because the stream lagged behind.
+	missed: Arc<AtomicU64>,
+}
+
+impl EventStream {
+	/// Wraps a [`broadcast::Receiver`], e.g. the one returned by
+	/// [`Server::start`](super::Server::start).
+	pub fn new(receiver: broadcast::Receiver<Event>) -> Self {
+		let missed = Arc::<AtomicU64>::default();
+		let counter = Arc::clone(&missed);
+
+		let inner = stream::unfold(receiver, move |mut receiver| {
+			let counter = Arc::clone(&counter);
+			async move {
+				loop {
+					match receiver.recv().await {
+						Ok(event) => return Some((event, receiver)),
+						Err(RecvError::Lagged(missed)) => {
+							counter.fetch_add(missed, Ordering::Relaxed);
+						}
+						Err(RecvError::Closed) => return None,
+					}
+				}
+			}
+		});
+
+		Self { inner: Box::pin(inner), missed }
+	}
+
+	/// How many events were missed because the stream lagged behind.
+	pub fn missed(&self) -> u64 {
+		self.missed.load(Ordering::Relaxed)
+	}
+
+	/// Only yields events in which `key` returns something different than for the previous event.
+	pub fn only_changes<K, F>(self, mut key: F) -> Self
+	where
+		K: PartialEq + Send + 'static,
+		F: FnMut(&Event) -> K + Send + 'static, {
+		let mut last = None;
+
+		self.map_inner(|inner| {
+			inner.filter(move |event| {
+				let key = Some(key(event));
+				let changed = key != last;
+				last = key;
+				future::ready(changed)
+			})
+		})
+	}
+
+	/// Skips heartbeats, i.e. events that are the same as the previous one apart from their
+	/// timestamp.
+	///
+	/// CS:GO sends these every [`Config::heartbeat`](super::Config::heartbeat) even if nothing
+	/// changed.
+	pub fn skip_heartbeats(self) -> Self {
+		self.only_changes(|event| Event {
+			provider: event
+				.provider
+				.as_ref()
+				.map(|provider| GameInfo { timestamp: 0, ..provider.clone() }),
+			delivery: None,
+			..event.clone()
+		})
+	}
+
+	/// Converts events into [`gokz::Event`](crate::gokz::Event)s, skipping those that can't be
+	/// converted.
+	#[cfg(feature = "gokz")]
+	pub fn gokz(self) -> impl Stream<Item = crate::gokz::Event> + Send {
+		self.filter_map(|event| future::ready(crate::gokz::Event::try_from(event).ok()))
+	}
+
+	fn map_inner<S>(self, f: impl FnOnce(Pin<Box<dyn Stream<Item = Event> + Send>>) -> S) -> Self
+	where
+		S: Stream<Item = Event> + Send + 'static, {
+		Self { inner: Box::pin(f(self.inner)), missed: self.missed }
+	}
+}
+
+impl Stream for EventStream {
+	type Item = Event;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.inner.as_mut().poll_next(cx)
+	}
+}
+
+impl From<broadcast::Receiver<Event>> for EventStream {
+	fn from(receiver: broadcast::Receiver<Event>) -> Self {
+		Self::new(receiver)
+	}
+}
+
+impl From<Receiver> for EventStream {
+	fn from(receiver: Receiver) -> Self {
+		let missed = Arc::<AtomicU64>::default();
+		let counter = Arc::clone(&missed);
+
+		let inner = stream::unfold(receiver, move |mut receiver| {
+			let counter = Arc::clone(&counter);
+			async move {
+				let event = receiver.recv().await?;
+				counter.store(receiver.missed(), Ordering::Relaxed);
+				Some((event, receiver))
+			}
+		});
+
+		Self { inner: Box::pin(inner), missed }
+	}
+}
+
+impl fmt::Debug for EventStream {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("EventStream")
+			.field("missed", &self.missed())
+			.finish_non_exhaustive()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::csgo::{test_util, Delivery},
+		serde_json::json,
+	};
+
+	/// Feeds `events` through a closed channel and collects what `stream` makes of them.
+	async fn collect<S, T>(events: Vec<Event>, stream: impl FnOnce(EventStream) -> S) -> Vec<T>
+	where
+		S: Stream<Item = T>, {
+		let (sender, receiver) = broadcast::channel(events.len().max(1));
+
+		for event in events {
+			sender.send(event).expect("receiver is alive");
+		}
+
+		drop(sender);
+		stream(EventStream::new(receiver)).collect().await
+	}
+
+	fn on_map(timestamp: u64, name: &str) -> Event {
+		test_util::from_json(json!({
+			"provider": test_util::provider(timestamp),
+			"map": test_util::map(name, 0),
+		}))
+	}
+
+	fn map_name(event: &Event) -> Option<String> {
+		event.map.as_ref().map(|map| map.name.clone())
+	}
+
+	#[tokio::test]
+	async fn only_changes() {
+		let events = vec![
+			on_map(1, "de_dust2"),
+			on_map(2, "de_dust2"),
+			on_map(3, "de_mirage"),
+			on_map(4, "de_dust2"),
+		];
+
+		let maps = collect(events, |stream| stream.only_changes(map_name)).await;
+		let timestamps = maps
+			.iter()
+			.filter_map(|event| event.provider.as_ref())
+			.map(|provider| provider.timestamp)
+			.collect::<Vec<_>>();
+
+		assert_eq!(timestamps, [1, 3, 4]);
+	}
+
+	#[tokio::test]
+	async fn skip_heartbeats() {
+		let mut duplicate = on_map(2, "de_dust2");
+		duplicate.delivery = Some(Delivery::now(b"heartbeat"));
+
+		let events = vec![
+			on_map(1, "de_dust2"),
+			on_map(2, "de_dust2"),
+			duplicate,
+			on_map(3, "de_mirage"),
+			test_util::event(4),
+		];
+
+		let events = collect(events, EventStream::skip_heartbeats).await;
+		let maps = events.iter().map(map_name).collect::<Vec<_>>();
+
+		assert_eq!(maps, [Some(String::from("de_dust2")), Some(String::from("de_mirage")), None]);
+	}
+
+	#[tokio::test]
+	async fn lagging_is_counted() {
+		let (sender, receiver) = broadcast::channel(2);
+		let mut stream = EventStream::new(receiver);
+
+		for timestamp in 0..5 {
+			sender
+				.send(test_util::event(timestamp))
+				.expect("receiver is alive");
+		}
+
+		drop(sender);
+
+		assert_eq!(stream.by_ref().count().await, 2);
+		assert_eq!(stream.missed(), 3);
+	}
+
+	#[cfg(feature = "gokz")]
+	#[tokio::test]
+	async fn gokz() {
+		let kz = test_util::from_json(json!({
+			"provider": test_util::provider(1),
+			"player": {
+				"name": "AlphaKeks",
+				"steamid": test_util::STEAM_ID,
+				"activity": "playing",
+			},
+			"map": test_util::map("workshop/123/kz_grotto", 0),
+		}));
+
+		let events = vec![test_util::event(0), kz];
+		let events = collect(events, EventStream::gokz).await;
+
+		assert_eq!(events.len(), 1);
+		assert_eq!(events[0].map_name.as_deref(), Some("kz_grotto"));
+		assert_eq!(
+			events[0].steam_id,
+			test_util::STEAM_ID
+				.parse()
+				.expect("valid SteamID")
+		);
+	}
+}
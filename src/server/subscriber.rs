@@ -0,0 +1,321 @@
+use {
+	super::{Filter, Metrics},
+	crate::csgo::Event,
+	std::sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+	tokio::sync::{broadcast, mpsc, watch},
+};
+
+/// What happens when a subscriber or [`EventHandler`](super::EventHandler) can't keep up with
+/// incoming events.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backpressure {
+	/// Once the subscriber's buffer is full, the oldest events are dropped to make room for new
+	/// ones. This is how [`Server::subscribe`](super::Server::subscribe) behaves.
+	#[default]
+	DropOldest,
+
+	/// Once the subscriber's buffer is full, the server doesn't respond to CS:GO until the
+	/// subscriber has made room for the new event.
+	///
+	/// No events are lost, but a slow subscriber slows down every other subscriber. If the
+	/// response takes longer than [`Config::timeout`](super::Config::timeout), CS:GO resends the
+	/// event, which is then dropped as a duplicate.
+	Block,
+
+	/// The subscriber only ever sees the latest event. Events that were superseded before the
+	/// subscriber got to them are skipped.
+	Coalesce,
+}
+
+/// Delivery statistics of a single subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriberStats {
+	/// Identifies the subscriber. Matches [`Receiver::id`].
+	pub id: u64,
+
+	/// The subscriber's backpressure policy.
+	pub policy: Backpressure,
+
+	/// How many events were sent to the subscriber.
+	pub sent: u64,
+
+	/// How many events the subscriber has received.
+	pub received: u64,
+
+	/// How many events the subscriber missed because it couldn't keep up.
+	///
+	/// This is only updated once the subscriber tries to receive another event.
+	pub missed: u64,
+}
+
+#[derive(Debug)]
+struct Counters {
+	sent: AtomicU64,
+	received: AtomicU64,
+	missed: AtomicU64,
+
+	/// The server's metrics, which keep counting missed events after the subscriber is gone.
+	metrics: Arc<Metrics>,
+}
+
+impl Counters {
+	fn new(metrics: Arc<Metrics>) -> Self {
+		Self {
+			sent: AtomicU64::default(),
+			received: AtomicU64::default(),
+			missed: AtomicU64::default(),
+			metrics,
+		}
+	}
+
+	fn missed(&self, missed: u64) {
+		self.missed.fetch_add(missed, Ordering::Relaxed);
+		self.metrics.missed(missed);
+	}
+
+	fn stats(&self, id: u64, policy: Backpressure) -> SubscriberStats {
+		SubscriberStats {
+			id,
+			policy,
+			sent: self.sent.load(Ordering::Relaxed),
+			received: self.received.load(Ordering::Relaxed),
+			missed: self.missed.load(Ordering::Relaxed),
+		}
+	}
+}
+
+/// Receives events from a [`Server`](super::Server) according to a [`Backpressure`] policy.
+///
+/// Obtained via [`Server::subscribe_with`](super::Server::subscribe_with).
+#[derive(Debug)]
+pub struct Receiver {
+	id: u64,
+	policy: Backpressure,
+	channel: ReceiverChannel,
+	counters: Arc<Counters>,
+}
+
+#[derive(Debug)]
+enum ReceiverChannel {
+	Broadcast(broadcast::Receiver<Event>),
+	Mpsc(mpsc::Receiver<Event>),
+	Watch { receiver: watch::Receiver<Option<(u64, Event)>>, last: u64 },
+}
+
+impl Receiver {
+	/// Waits for the next event.
+	///
+	/// Returns [`None`] once the server has been dropped.
+	pub async fn recv(&mut self) -> Option<Event> {
+		let event = match &mut self.channel {
+			ReceiverChannel::Broadcast(receiver) => loop {
+				match receiver.recv().await {
+					Ok(event) => break event,
+					Err(broadcast::error::RecvError::Lagged(missed)) => {
+						self.counters.missed(missed);
+					}
+					Err(broadcast::error::RecvError::Closed) => return None,
+				}
+			},
+			ReceiverChannel::Mpsc(receiver) => receiver.recv().await?,
+			ReceiverChannel::Watch { receiver, last } => {
+				receiver.changed().await.ok()?;
+
+				let (sequence, event) = receiver.borrow_and_update().clone()?;
+
+				self.counters
+					.missed(sequence.saturating_sub(*last + 1));
+				*last = sequence;
+
+				event
+			}
+		};
+
+		self.counters
+			.received
+			.fetch_add(1, Ordering::Relaxed);
+
+		Some(event)
+	}
+
+	/// Identifies this subscriber in [`Server::subscribers`](super::Server::subscribers).
+	pub fn id(&self) -> u64 {
+		self.id
+	}
+
+	/// The backpressure policy of this subscriber.
+	pub fn policy(&self) -> Backpressure {
+		self.policy
+	}
+
+	/// Delivery statistics of this subscriber.
+	pub fn stats(&self) -> SubscriberStats {
+		self.counters.stats(self.id, self.policy)
+	}
+
+	/// How many events this subscriber missed because it couldn't keep up.
+	pub fn missed(&self) -> u64 {
+		self.counters.missed.load(Ordering::Relaxed)
+	}
+}
+
+/// The sending half of a subscriber's channel.
+#[derive(Debug, Clone)]
+enum SenderChannel {
+	Broadcast(broadcast::Sender<Event>),
+	Mpsc(mpsc::Sender<Event>),
+	Watch { sender: Arc<watch::Sender<Option<(u64, Event)>>>, sequence: Arc<AtomicU64> },
+}
+
+impl SenderChannel {
+	fn is_closed(&self) -> bool {
+		match self {
+			Self::Broadcast(sender) => sender.receiver_count() == 0,
+			Self::Mpsc(sender) => sender.is_closed(),
+			Self::Watch { sender, .. } => sender.is_closed(),
+		}
+	}
+}
+
+/// A subscriber that only receives some events.
+#[derive(Debug, Clone)]
+struct Subscriber {
+	id: u64,
+	filter: Filter,
+	policy: Backpressure,
+	channel: SenderChannel,
+	counters: Arc<Counters>,
+}
+
+impl Subscriber {
+	async fn send(&self, event: Event) {
+		match &self.channel {
+			SenderChannel::Broadcast(sender) => {
+				_ = sender.send(event);
+			}
+			SenderChannel::Mpsc(sender) => {
+				_ = sender.send(event).await;
+			}
+			SenderChannel::Watch { sender, sequence } => {
+				let sequence = sequence.fetch_add(1, Ordering::Relaxed) + 1;
+				sender.send_replace(Some((sequence, event)));
+			}
+		}
+
+		self.counters.sent.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+/// All subscribers registered via [`Server::subscribe_filtered`](super::Server::subscribe_filtered)
+/// and [`Server::subscribe_with`](super::Server::subscribe_with).
+#[derive(Debug)]
+pub(crate) struct Subscribers {
+	subscribers: Mutex<Vec<Subscriber>>,
+	next_id: AtomicU64,
+	metrics: Arc<Metrics>,
+}
+
+impl Subscribers {
+	pub(crate) fn new(metrics: Arc<Metrics>) -> Self {
+		Self { subscribers: Mutex::default(), next_id: AtomicU64::default(), metrics }
+	}
+
+	/// Registers a new subscriber with a [`Backpressure::DropOldest`] policy and a message buffer
+	/// of `capacity`.
+	pub(crate) fn subscribe(&self, filter: Filter, capacity: usize) -> broadcast::Receiver<Event> {
+		let (sender, receiver) = broadcast::channel(capacity);
+		self.register(filter, Backpressure::DropOldest, SenderChannel::Broadcast(sender));
+		receiver
+	}
+
+	/// Registers a new subscriber with the given `policy` and a message buffer of `capacity`.
+	pub(crate) fn subscribe_with(
+		&self,
+		filter: Filter,
+		policy: Backpressure,
+		capacity: usize,
+	) -> Receiver {
+		let (sender, receiver) = match policy {
+			Backpressure::DropOldest => {
+				let (sender, receiver) = broadcast::channel(capacity);
+				(SenderChannel::Broadcast(sender), ReceiverChannel::Broadcast(receiver))
+			}
+			Backpressure::Block => {
+				let (sender, receiver) = mpsc::channel(capacity);
+				(SenderChannel::Mpsc(sender), ReceiverChannel::Mpsc(receiver))
+			}
+			Backpressure::Coalesce => {
+				let (sender, receiver) = watch::channel(None);
+				let sender =
+					SenderChannel::Watch { sender: Arc::new(sender), sequence: Arc::default() };
+				(sender, ReceiverChannel::Watch { receiver, last: 0 })
+			}
+		};
+
+		let (id, counters) = self.register(filter, policy, sender);
+
+		Receiver { id, policy, channel: receiver, counters }
+	}
+
+	/// Delivery statistics of all subscribers that are still alive.
+	pub(crate) fn stats(&self) -> Vec<SubscriberStats> {
+		self.lock()
+			.iter()
+			.filter(|subscriber| !subscriber.channel.is_closed())
+			.map(|subscriber| {
+				subscriber
+					.counters
+					.stats(subscriber.id, subscriber.policy)
+			})
+			.collect()
+	}
+
+	/// Sends `event` to every subscriber whose filter it passes.
+	///
+	/// Subscribers whose receivers have all been dropped are removed. Waits for subscribers with
+	/// a [`Backpressure::Block`] policy to make room for the event.
+	pub(crate) async fn dispatch(&self, event: &Event, previous: Option<&Event>) {
+		let recipients = {
+			let mut subscribers = self.lock();
+			subscribers.retain(|subscriber| !subscriber.channel.is_closed());
+			subscribers
+				.iter()
+				.filter(|subscriber| subscriber.filter.matches(event, previous))
+				.cloned()
+				.collect::<Vec<_>>()
+		};
+
+		for subscriber in recipients {
+			subscriber.send(event.clone()).await;
+		}
+	}
+
+	fn register(
+		&self,
+		filter: Filter,
+		policy: Backpressure,
+		channel: SenderChannel,
+	) -> (u64, Arc<Counters>) {
+		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+		let counters = Arc::new(Counters::new(Arc::clone(&self.metrics)));
+
+		self.lock().push(Subscriber {
+			id,
+			filter,
+			policy,
+			channel,
+			counters: Arc::clone(&counters),
+		});
+
+		(id, counters)
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Subscriber>> {
+		self.subscribers
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
@@ -0,0 +1,57 @@
+//! Helpers for testing the [`Server::router`] shared by unit tests.
+
+// Not every feature combination has tests using every helper.
+#![allow(dead_code)]
+
+use {
+	super::Server,
+	axum::{
+		body::Body,
+		http::{header, Request, Response, StatusCode},
+	},
+	hyper::body::HttpBody,
+	tower::ServiceExt,
+};
+
+/// A `GET` request for `uri`.
+pub(crate) fn get(uri: &str) -> Request<Body> {
+	Request::get(uri)
+		.body(Body::empty())
+		.expect("valid request")
+}
+
+/// A `GET` request for `uri` with `token` as bearer token.
+pub(crate) fn get_with_token(uri: &str, token: &str) -> Request<Body> {
+	Request::get(uri)
+		.header(header::AUTHORIZATION, format!("Bearer {token}"))
+		.body(Body::empty())
+		.expect("valid request")
+}
+
+/// Sends `request` to the router of `server`.
+pub(crate) async fn send(server: &Server, request: Request<Body>) -> Response<axum::body::BoxBody> {
+	server
+		.router::<()>()
+		.oneshot(request)
+		.await
+		.expect("routers are infallible")
+}
+
+/// Sends `request` to the router of `server` and returns the status code.
+pub(crate) async fn status(server: &Server, request: Request<Body>) -> StatusCode {
+	send(server, request).await.status()
+}
+
+/// Reads the first chunk of the response body.
+///
+/// Unlike reading the whole body, this also works for streaming responses.
+pub(crate) async fn first_chunk(response: Response<axum::body::BoxBody>) -> String {
+	let chunk = response
+		.into_body()
+		.data()
+		.await
+		.expect("body is not empty")
+		.expect("body can be read");
+
+	String::from_utf8(chunk.to_vec()).expect("body is UTF-8")
+}
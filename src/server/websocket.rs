@@ -0,0 +1,166 @@
+//! WebSocket route rebroadcasting events to e.g. browser overlays.
+
+use {
+	super::{
+		render::{Params, Sections},
+		Server,
+	},
+	crate::Event,
+	axum::{
+		extract::{
+			ws::{Message, WebSocket, WebSocketUpgrade},
+			Query, State,
+		},
+		response::Response,
+	},
+	std::sync::Arc,
+	tokio::sync::broadcast::{self, error::RecvError},
+	tracing::debug,
+};
+
+pub(crate) async fn handler(
+	State(server): State<Arc<Server>>,
+	Query(params): Query<Params>,
+	upgrade: WebSocketUpgrade,
+) -> Response {
+	let sections = Sections::new(&params);
+	upgrade.on_upgrade(move |socket| connection(server, sections, socket))
+}
+
+/// Sends the latest event, followed by every event in which the requested sections changed.
+async fn connection(server: Arc<Server>, sections: Sections, socket: WebSocket) {
+	debug!("websocket connected");
+
+	let mut events = server.subscribe();
+	let mut connection =
+		Connection { server: Arc::clone(&server), sections, socket, last_sent: None };
+
+	if let Some(latest) = server.latest() {
+		if !connection.send(&latest).await {
+			return;
+		}
+	}
+
+	while let Some(event) = connection.next(&mut events).await {
+		if !connection.send(&event).await {
+			break;
+		}
+	}
+
+	debug!("websocket disconnected");
+}
+
+/// A single WebSocket connection.
+struct Connection {
+	server: Arc<Server>,
+	sections: Sections,
+	socket: WebSocket,
+
+	/// The last message sent to this connection.
+	last_sent: Option<String>,
+}
+
+impl Connection {
+	/// Waits for the next event to send.
+	///
+	/// Returns [`None`] once the connection or the server has been closed.
+	async fn next(&mut self, events: &mut broadcast::Receiver<Event>) -> Option<Event> {
+		loop {
+			tokio::select! {
+				event = events.recv() => match event {
+					Ok(event) => return Some(event),
+					Err(RecvError::Lagged(missed)) => {
+						debug!(missed, "websocket connection lagged behind");
+						self.server.metrics.missed(missed);
+					}
+					Err(RecvError::Closed) => return None,
+				},
+				message = self.socket.recv() => match message {
+					Some(Ok(Message::Close(_)) | Err(_)) | None => return None,
+					Some(Ok(_)) => {}
+				},
+			}
+		}
+	}
+
+	/// Sends `event`, unless the requested sections didn't change since the last message.
+	///
+	/// Returns `false` if the connection has been closed.
+	async fn send(&mut self, event: &Event) -> bool {
+		let Some(message) = self.sections.render(event) else {
+			return true;
+		};
+
+		if self.last_sent.as_ref() == Some(&message) {
+			return true;
+		}
+
+		if self
+			.socket
+			.send(Message::Text(message.clone()))
+			.await
+			.is_err()
+		{
+			return false;
+		}
+
+		self.last_sent = Some(message);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::server::{test_util, Config},
+		axum::{
+			body::Body,
+			http::{header, Request, StatusCode},
+		},
+	};
+
+	fn server() -> Server {
+		Server::builder()
+			.websocket("/ws")
+			.reader_token("secret")
+			.build(Config::builder().build("test", 8080_u16))
+	}
+
+	fn upgrade(uri: &str) -> Request<Body> {
+		Request::get(uri)
+			.header(header::CONNECTION, "upgrade")
+			.header(header::UPGRADE, "websocket")
+			.header(header::SEC_WEBSOCKET_VERSION, "13")
+			.header(header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ==")
+			.body(Body::empty())
+			.expect("valid request")
+	}
+
+	#[tokio::test]
+	async fn reader_token_is_required() {
+		let server = server();
+
+		assert_eq!(test_util::status(&server, upgrade("/ws")).await, StatusCode::UNAUTHORIZED);
+		assert_eq!(
+			test_util::status(&server, upgrade("/ws?token=guess")).await,
+			StatusCode::UNAUTHORIZED
+		);
+	}
+
+	#[tokio::test]
+	async fn only_upgrades_are_accepted() {
+		let server = server();
+
+		// `oneshot` can't hand out the underlying connection, so even valid handshakes stop at
+		// the upgrade. Getting that far means the request passed the access checks.
+		assert_eq!(
+			test_util::status(&server, upgrade("/ws?token=secret&sections=map")).await,
+			StatusCode::UPGRADE_REQUIRED
+		);
+		assert_eq!(
+			test_util::status(&server, test_util::get_with_token("/ws", "secret")).await,
+			StatusCode::BAD_REQUEST
+		);
+	}
+}
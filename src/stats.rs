@@ -0,0 +1,508 @@
+//! Per-round and per-match statistics for the current player.
+//!
+//! CS:GO only reports snapshots of the current round and match. [`StatsTracker`] consumes the
+//! event stream and keeps a history of finished rounds to compute aggregates like ADR, headshot
+//! percentage or K/D from.
+
+use crate::csgo::{Event, GameInfo, GamePhase, MatchPlayer, MatchStats, Player, RoundPhase, Team};
+
+/// Stats of the current player for a single round.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundStats {
+	/// The round number, starting at 1.
+	pub round: u64,
+
+	/// The team the player played on.
+	pub team: Option<Team>,
+
+	/// How many kills the player got.
+	///
+	/// Can be negative if the player killed teammates.
+	pub kills: isize,
+
+	/// How many of those kills were headshots.
+	pub headshots: usize,
+
+	/// How much damage the player dealt.
+	pub damage: usize,
+
+	/// Whether the player was still alive at the end of the round.
+	pub survived: bool,
+
+	/// Whether the player was the MVP of the round.
+	pub mvp: bool,
+
+	/// Whether the player's team won the round.
+	///
+	/// Is [`None`] if the round ended without a winner being reported.
+	pub won: Option<bool>,
+
+	/// The clutch situation the player was in, if any.
+	pub clutch: Option<Clutch>,
+}
+
+impl RoundStats {
+	fn new(round: u64) -> Self {
+		Self {
+			round,
+			team: None,
+			kills: 0,
+			headshots: 0,
+			damage: 0,
+			survived: true,
+			mvp: false,
+			won: None,
+			clutch: None,
+		}
+	}
+}
+
+/// A situation in which the player was the last one alive on their team.
+///
+/// Can only be detected if [`Event::all_players`] is sent and contains the player, which CS:GO
+/// only does while spectating. Clutches of players that play the game themselves are usually not
+/// detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clutch {
+	/// How many opponents were alive when the clutch started.
+	pub opponents: usize,
+
+	/// Whether the player's team won the round.
+	pub won: bool,
+}
+
+impl Clutch {
+	/// Checks whether the player providing `event` is the only one alive on their team while
+	/// facing at least one opponent.
+	fn detect(event: &Event, provider: &GameInfo) -> Option<Self> {
+		let is_alive = |player: &&MatchPlayer| player.state.is_some_and(|state| state.health > 0);
+
+		let player = event
+			.all_players
+			.iter()
+			.find(|(steam_id, _)| is_provider(steam_id, provider))
+			.map(|(_, player)| player)
+			.filter(is_alive)?;
+
+		let team = player.team.as_ref()?;
+
+		let (teammates, opponents) = event.all_players.values().filter(is_alive).fold(
+			(0, 0),
+			|(teammates, opponents), player| match player.team.as_ref() {
+				Some(player_team) if player_team == team => (teammates + 1, opponents),
+				_ => (teammates, opponents + 1),
+			},
+		);
+
+		(teammates == 1 && opponents > 0).then_some(Self { opponents, won: false })
+	}
+}
+
+/// How many rounds the player finished with multiple kills.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiKills {
+	/// Rounds with exactly 2 kills.
+	pub two: usize,
+
+	/// Rounds with exactly 3 kills.
+	pub three: usize,
+
+	/// Rounds with exactly 4 kills.
+	pub four: usize,
+
+	/// Rounds with 5 or more kills.
+	pub five: usize,
+}
+
+/// Aggregated stats of the current player for the whole match.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchSummary {
+	/// The amount of finished rounds.
+	pub rounds: usize,
+
+	/// How many kills the player has made.
+	pub kills: isize,
+
+	/// How many kills the player has assisted with.
+	pub assists: usize,
+
+	/// How many times the player has died.
+	pub deaths: usize,
+
+	/// How many times the player has been MVP.
+	///
+	/// Like the other match totals, this includes rounds played before tracking started.
+	pub mvps: usize,
+
+	/// The player's score.
+	pub score: usize,
+
+	/// The total damage the player has dealt.
+	pub damage: usize,
+
+	/// How many kills were headshots.
+	pub headshots: usize,
+
+	/// Average damage per round.
+	pub adr: f64,
+
+	/// Percentage (`0.0` to `100.0`) of kills that were headshots.
+	pub headshot_percentage: f64,
+
+	/// Kills per death.
+	pub kd_ratio: f64,
+
+	/// Percentage (`0.0` to `100.0`) of finished rounds the player was MVP in.
+	///
+	/// Unlike [`MatchSummary::mvps`], this only considers the rounds that were tracked.
+	pub mvp_rate: f64,
+
+	/// Rounds with multiple kills.
+	pub multi_kills: MultiKills,
+
+	/// How many clutch situations the player was in.
+	pub clutches: usize,
+
+	/// How many of those clutches the player won.
+	pub clutches_won: usize,
+}
+
+/// Whether `steam_id`, a key of [`Event::all_players`], belongs to the player providing events.
+#[cfg(not(feature = "gokz"))]
+fn is_provider(steam_id: &str, provider: &GameInfo) -> bool {
+	steam_id == provider.steam_id
+}
+
+/// Whether `steam_id`, a key of [`Event::all_players`], belongs to the player providing events.
+#[cfg(feature = "gokz")]
+fn is_provider(steam_id: &str, provider: &GameInfo) -> bool {
+	steam_id
+		.parse::<gokz_rs::SteamID>()
+		.is_ok_and(|steam_id| steam_id == provider.steam_id)
+}
+
+/// Tracks the current player's stats across events.
+///
+/// Round and match progress is taken from every event outside of warmup. Per-player stats are only
+/// taken from events about the current player, not from events about a player they are spectating
+/// (e.g. after dying).
+#[derive(Debug, Default, Clone)]
+pub struct StatsTracker {
+	/// The round that is currently being played.
+	current: Option<RoundStats>,
+
+	/// All finished rounds.
+	rounds: Vec<RoundStats>,
+
+	/// The last reported stats for the whole match.
+	match_stats: Option<MatchStats>,
+
+	/// The match stats at the start of the current round.
+	round_start: Option<MatchStats>,
+
+	/// The round phase of the last event.
+	last_phase: Option<RoundPhase>,
+}
+
+impl StatsTracker {
+	/// Constructs a new [`StatsTracker`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Updates the tracker with a new event.
+	pub fn update(&mut self, event: &Event) {
+		let Some(map) = event.map.as_ref() else {
+			return;
+		};
+
+		if map.phase == GamePhase::Warmup {
+			return;
+		}
+
+		let phase = event.round.as_ref().map(|round| &round.phase);
+		let round = u64::from(map.round) + 1;
+
+		// A new match has started.
+		if self
+			.rounds
+			.last()
+			.is_some_and(|last| last.round >= round)
+		{
+			self.reset();
+		}
+
+		// A new round has started.
+		if phase == Some(&RoundPhase::FreezeTime) && self.last_phase != Some(RoundPhase::FreezeTime)
+		{
+			self.finish_round();
+		}
+
+		self.last_phase = phase.cloned();
+		self.current
+			.get_or_insert_with(|| RoundStats::new(round));
+
+		// While the current player is dead, `player` is whoever they are spectating.
+		let player = event.player.as_ref().filter(|player| {
+			event
+				.provider
+				.as_ref()
+				.is_none_or(|provider| provider.steam_id == player.steam_id)
+		});
+
+		if let Some(player) = player {
+			self.update_player(player);
+		}
+
+		let Some(current) = self.current.as_mut() else {
+			return;
+		};
+
+		if current.survived && current.clutch.is_none() {
+			current.clutch = event
+				.provider
+				.as_ref()
+				.and_then(|provider| Clutch::detect(event, provider));
+		}
+
+		if let Some(event_round) = event.round.as_ref() {
+			if event_round.phase == RoundPhase::Over {
+				if let Some(winner) = event_round.winner.as_ref() {
+					let won = current.team.as_ref() == Some(winner);
+					current.won = Some(won);
+
+					if let Some(clutch) = current.clutch.as_mut() {
+						clutch.won = won;
+					}
+				}
+			}
+		}
+	}
+
+	/// The round that is currently being played.
+	pub fn current_round(&self) -> Option<&RoundStats> {
+		self.current.as_ref()
+	}
+
+	/// All finished rounds, in order.
+	pub fn rounds(&self) -> &[RoundStats] {
+		&self.rounds
+	}
+
+	/// The last reported stats for the whole match.
+	pub fn match_stats(&self) -> Option<&MatchStats> {
+		self.match_stats.as_ref()
+	}
+
+	/// Aggregates the stats of all finished rounds.
+	pub fn summary(&self) -> MatchSummary {
+		let mut summary = MatchSummary { rounds: self.rounds.len(), ..Default::default() };
+
+		if let Some(match_stats) = self.match_stats {
+			summary.kills = match_stats.kills;
+			summary.assists = match_stats.assists;
+			summary.deaths = match_stats.deaths;
+			summary.mvps = match_stats.mvps;
+			summary.score = match_stats.score;
+		}
+
+		let mut round_kills = 0;
+		let mut mvp_rounds = 0;
+
+		for round in &self.rounds {
+			summary.damage += round.damage;
+			summary.headshots += round.headshots;
+			round_kills += round.kills.max(0) as usize;
+			mvp_rounds += usize::from(round.mvp);
+
+			match round.kills {
+				2 => summary.multi_kills.two += 1,
+				3 => summary.multi_kills.three += 1,
+				4 => summary.multi_kills.four += 1,
+				5.. => summary.multi_kills.five += 1,
+				_ => {}
+			}
+
+			if let Some(clutch) = round.clutch {
+				summary.clutches += 1;
+				summary.clutches_won += usize::from(clutch.won);
+			}
+		}
+
+		if summary.rounds > 0 {
+			summary.adr = summary.damage as f64 / summary.rounds as f64;
+			summary.mvp_rate = mvp_rounds as f64 / summary.rounds as f64 * 100.0;
+		}
+
+		if round_kills > 0 {
+			summary.headshot_percentage = summary.headshots as f64 / round_kills as f64 * 100.0;
+		}
+
+		summary.kd_ratio = summary.kills as f64 / summary.deaths.max(1) as f64;
+
+		summary
+	}
+
+	/// Clears all tracked stats, e.g. because a new match started.
+	pub fn reset(&mut self) {
+		*self = Self::default();
+	}
+
+	/// Updates the current round with the stats of the current player.
+	fn update_player(&mut self, player: &Player) {
+		let Some(current) = self.current.as_mut() else {
+			return;
+		};
+
+		current.team = player.team.clone();
+
+		if let Some(state) = player.state.as_ref() {
+			current.kills = state.kills;
+			current.headshots = state.headshots;
+			current.damage = state.round_damage.unwrap_or(current.damage);
+			current.survived = state.health > 0;
+		}
+
+		if let Some(match_stats) = player.match_stats {
+			let round_start = *self.round_start.get_or_insert(match_stats);
+			current.mvp = match_stats.mvps > round_start.mvps;
+			current.survived &= match_stats.deaths == round_start.deaths;
+			self.match_stats = Some(match_stats);
+		}
+	}
+
+	/// Moves the current round into the history.
+	fn finish_round(&mut self) {
+		if let Some(round) = self.current.take() {
+			self.rounds.push(round);
+		}
+
+		self.round_start = self.match_stats;
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use {
+		super::*,
+		crate::csgo::test_util::{self, STEAM_ID as LOCAL},
+		serde_json::{json, Value},
+	};
+
+	const TEAMMATE: &str = "76561198000000002";
+	const OPPONENT: &str = "76561198000000003";
+
+	fn match_player(team: &str, health: u8) -> Value {
+		json!({ "name": "player", "team": team, "state": test_util::player_state(health) })
+	}
+
+	/// An event sent by [`LOCAL`] while `player` is the one being watched.
+	fn event(
+		player: &str,
+		health: u8,
+		phase: &str,
+		winner: Option<&str>,
+		all_players: Value,
+	) -> Event {
+		test_util::from_json(json!({
+			"provider": test_util::provider(0),
+			"player": {
+				"name": "player",
+				"steamid": player,
+				"activity": "playing",
+				"team": "CT",
+				"state": test_util::player_state(health),
+			},
+			"map": test_util::map("de_mirage", 0),
+			"round": { "phase": phase, "win_team": winner },
+			"allplayers": all_players,
+		}))
+	}
+
+	#[test]
+	fn round_result_is_taken_while_spectating() {
+		let mut tracker = StatsTracker::new();
+
+		tracker.update(&event(LOCAL, 100, "live", None, json!({})));
+		tracker.update(&event(LOCAL, 0, "live", None, json!({})));
+		tracker.update(&event(TEAMMATE, 100, "over", Some("CT"), json!({})));
+
+		let round = tracker.current_round().expect("round is tracked");
+		assert_eq!(round.team, Some(Team::CT));
+		assert_eq!(round.won, Some(true));
+		assert!(!round.survived);
+	}
+
+	#[test]
+	fn spectated_player_stats_are_ignored() {
+		let mut tracker = StatsTracker::new();
+
+		tracker.update(&event(LOCAL, 0, "live", None, json!({})));
+		tracker.update(&event(TEAMMATE, 100, "live", None, json!({})));
+
+		assert!(
+			!tracker
+				.current_round()
+				.expect("round is tracked")
+				.survived
+		);
+	}
+
+	#[test]
+	fn clutch_is_detected_from_all_players() {
+		let mut tracker = StatsTracker::new();
+		let all_players = json!({
+			LOCAL: match_player("CT", 50),
+			TEAMMATE: match_player("CT", 0),
+			OPPONENT: match_player("T", 100),
+		});
+
+		tracker.update(&event(LOCAL, 50, "live", None, all_players.clone()));
+		tracker.update(&event(LOCAL, 50, "over", Some("CT"), all_players));
+
+		let round = tracker.current_round().expect("round is tracked");
+		assert_eq!(round.clutch, Some(Clutch { opponents: 1, won: true }));
+	}
+
+	#[test]
+	fn no_clutch_while_teammates_are_alive() {
+		let mut tracker = StatsTracker::new();
+		let all_players = json!({
+			LOCAL: match_player("CT", 50),
+			TEAMMATE: match_player("CT", 100),
+			OPPONENT: match_player("T", 100),
+		});
+
+		tracker.update(&event(LOCAL, 50, "live", None, all_players));
+
+		assert_eq!(
+			tracker
+				.current_round()
+				.expect("round is tracked")
+				.clutch,
+			None
+		);
+	}
+
+	#[test]
+	fn summary_only_rates_tracked_rounds() {
+		let round = |mvp, damage| RoundStats { mvp, damage, ..RoundStats::new(1) };
+		let tracker = StatsTracker {
+			rounds: vec![round(true, 100), round(false, 50)],
+			// Tracking started in the middle of the match.
+			match_stats: Some(MatchStats { kills: 20, deaths: 10, mvps: 10, ..Default::default() }),
+			..Default::default()
+		};
+
+		let summary = tracker.summary();
+		assert_eq!(summary.rounds, 2);
+		assert_eq!(summary.mvps, 10);
+		assert_eq!(summary.mvp_rate, 50.0);
+		assert_eq!(summary.adr, 75.0);
+		assert_eq!(summary.kd_ratio, 2.0);
+	}
+}